@@ -0,0 +1,153 @@
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use smallvec::SmallVec;
+use thin_vec::ThinVec;
+use vekk::Vekk;
+
+fn push_pop(c: &mut Criterion) {
+    let mut group = c.benchmark_group("push_pop");
+
+    group.bench_function("vekk", |b| {
+        b.iter(|| {
+            let mut v: Vekk<[u64; 8]> = Vekk::default();
+            for i in 0..8 {
+                v.push(black_box(i));
+            }
+            while let Some(item) = v.pop() {
+                black_box(item);
+            }
+        })
+    });
+    group.bench_function("vec", |b| {
+        b.iter(|| {
+            let mut v: Vec<u64> = Vec::new();
+            for i in 0..8 {
+                v.push(black_box(i));
+            }
+            while let Some(item) = v.pop() {
+                black_box(item);
+            }
+        })
+    });
+    group.bench_function("smallvec", |b| {
+        b.iter(|| {
+            let mut v: SmallVec<[u64; 8]> = SmallVec::new();
+            for i in 0..8 {
+                v.push(black_box(i));
+            }
+            while let Some(item) = v.pop() {
+                black_box(item);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+fn extend(c: &mut Criterion) {
+    for len in [8, 64, 1024] {
+        let mut group = c.benchmark_group(format!("extend_{len}"));
+
+        group.bench_function("vekk", |b| {
+            b.iter(|| {
+                let mut v: Vekk<[u64; 8]> = Vekk::default();
+                v.extend(0..black_box(len));
+                v
+            })
+        });
+        group.bench_function("vec", |b| {
+            b.iter(|| {
+                let mut v: Vec<u64> = Vec::new();
+                v.extend(0..black_box(len));
+                v
+            })
+        });
+        group.bench_function("smallvec", |b| {
+            b.iter(|| {
+                let mut v: SmallVec<[u64; 8]> = SmallVec::new();
+                v.extend(0..black_box(len));
+                v
+            })
+        });
+
+        group.finish();
+    }
+}
+
+fn clear(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clear_256");
+
+    group.bench_function("vekk", |b| {
+        b.iter_batched_ref(
+            || -> Vekk<[u8; 256]> { core::iter::repeat_n(1, 256).collect() },
+            |v| v.clear(),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("smallvec", |b| {
+        b.iter_batched_ref(
+            || -> SmallVec<[u8; 256]> { core::iter::repeat_n(1, 256).collect() },
+            |v| v.clear(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+fn clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_inline_256");
+
+    let vekk: Vekk<[u8; 256]> = (0..=255).collect();
+    group.bench_function("vekk", |b| b.iter(|| black_box(&vekk).clone()));
+    group.bench_function("vekk_per_element", |b| {
+        b.iter(|| -> Vekk<[u8; 256]> { black_box(&vekk).iter().copied().collect() })
+    });
+
+    let smallvec: SmallVec<[u8; 256]> = (0..=255).collect();
+    group.bench_function("smallvec", |b| b.iter(|| black_box(&smallvec).clone()));
+
+    group.finish();
+}
+
+fn retain(c: &mut Criterion) {
+    let keep = |item: &u64| !item.is_multiple_of(3);
+
+    let mut group = c.benchmark_group("retain_inline_32");
+    group.bench_function("vekk", |b| {
+        b.iter_batched_ref(
+            || -> Vekk<[u64; 32]> { (0..32).collect() },
+            |v| v.retain(keep),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("smallvec", |b| {
+        b.iter_batched_ref(
+            || -> SmallVec<[u64; 32]> { (0..32).collect() },
+            |v| v.retain(|item| keep(item)),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("retain_heap_1024");
+    group.bench_function("vekk", |b| {
+        b.iter_batched_ref(
+            || -> Vekk<[u64; 8]> { (0..1024).collect() },
+            |v| v.retain(keep),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("thin_vec_retain", |b| {
+        b.iter_batched_ref(
+            || -> ThinVec<u64> { (0..1024).collect() },
+            |v| v.retain(keep),
+            BatchSize::SmallInput,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, push_pop, extend, clear, clone, retain);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Names from `smallvec`, to ease migrating with a single import change:
+//! `use vekk::compat::{SmallVec, SmallVecExt};`.
+//!
+//! Enabled by the `compat` feature. The smallvec-specific methods live on the
+//! `SmallVecExt` trait rather than on `Vekk` itself, so enabling the feature doesn't
+//! change the inherent API, and later inherent methods can't clash with them.
+//!
+//! There is no `new_const`: the inline array is built through `Array::default`, and
+//! trait methods can't be called in a const context. Use `SmallVec::new` instead.
+
+use alloc::vec::Vec;
+
+use crate::{Array, Repr, Vekk};
+
+pub type SmallVec<A> = Vekk<A>;
+
+pub trait SmallVecExt<A: Array>: Sized {
+    fn from_buf(buf: A) -> Self;
+
+    fn from_vec(vec: Vec<A::Item>) -> Self
+    where
+        A::Item: Default;
+
+    fn inline_size(&self) -> usize;
+
+    fn spilled(&self) -> bool;
+}
+
+impl<A: Array> SmallVecExt<A> for Vekk<A> {
+    fn from_buf(buf: A) -> Self {
+        buf.into()
+    }
+
+    fn from_vec(vec: Vec<A::Item>) -> Self
+    where
+        A::Item: Default,
+    {
+        vec.into()
+    }
+
+    fn inline_size(&self) -> usize {
+        Self::inline_capacity()
+    }
+
+    fn spilled(&self) -> bool {
+        matches!(self.repr, Repr::Heap(_))
+    }
+}
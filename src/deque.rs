@@ -0,0 +1,77 @@
+use crate::{Array, Vekk};
+
+pub struct VekkDeque<A: Array> {
+    vekk: Vekk<A>,
+}
+
+impl<A: Array> VekkDeque<A> {
+    pub fn len(&self) -> usize {
+        self.vekk.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vekk.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[A::Item] {
+        self.vekk.as_slice()
+    }
+
+    pub fn front(&self) -> Option<&A::Item> {
+        self.vekk.first()
+    }
+
+    pub fn back(&self) -> Option<&A::Item> {
+        self.vekk.last()
+    }
+
+    pub fn push_front(&mut self, item: A::Item)
+    where
+        A::Item: Default,
+    {
+        self.vekk.insert(0, item);
+    }
+
+    pub fn push_back(&mut self, item: A::Item)
+    where
+        A::Item: Default,
+    {
+        self.vekk.push(item);
+    }
+
+    pub fn pop_front(&mut self) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        if self.vekk.is_empty() {
+            None
+        } else {
+            Some(self.vekk.remove(0))
+        }
+    }
+
+    pub fn pop_back(&mut self) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        self.vekk.pop()
+    }
+
+    pub fn into_inner(self) -> Vekk<A> {
+        self.vekk
+    }
+}
+
+impl<A: Array> Default for VekkDeque<A> {
+    fn default() -> Self {
+        Self {
+            vekk: Vekk::default(),
+        }
+    }
+}
+
+impl<A: Array> From<Vekk<A>> for VekkDeque<A> {
+    fn from(vekk: Vekk<A>) -> Self {
+        Self { vekk }
+    }
+}
@@ -0,0 +1,43 @@
+//! Global hook for observing inline/heap transitions.
+//!
+//! Enabled by the `instrument` feature. Useful for finding call sites that spill
+//! unexpectedly, and for tuning inline array sizes.
+
+use std::sync::RwLock;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Transition {
+    /// The elements moved from the inline array to the heap.
+    Spill,
+    /// The elements moved from the heap back into the inline array.
+    Compact,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SpillEvent {
+    /// The type name of the backing array, e.g. `[u32; 4]`.
+    pub type_name: &'static str,
+    pub transition: Transition,
+    /// The length of the vector after the transition.
+    pub len: usize,
+    pub inline_capacity: usize,
+}
+
+static SPILL_HOOK: RwLock<Option<fn(&SpillEvent)>> = RwLock::new(None);
+
+/// Install a hook invoked on every inline/heap transition of any `Vekk`, or remove it with `None`.
+pub fn set_spill_hook(hook: Option<fn(&SpillEvent)>) {
+    *SPILL_HOOK.write().unwrap_or_else(|err| err.into_inner()) = hook;
+}
+
+pub(crate) fn report<A>(transition: Transition, len: usize, inline_capacity: usize) {
+    let hook = *SPILL_HOOK.read().unwrap_or_else(|err| err.into_inner());
+    if let Some(hook) = hook {
+        hook(&SpillEvent {
+            type_name: core::any::type_name::<A>(),
+            transition,
+            len,
+            inline_capacity,
+        });
+    }
+}
@@ -1,4 +1,6 @@
-use std::ops::Deref;
+use core::iter::FusedIterator;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
 
 use thin_vec::ThinVec;
 
@@ -11,10 +13,35 @@ enum IterRepr<A: Array> {
     Heap(<ThinVec<A::Item> as IntoIterator>::IntoIter),
 }
 
-impl<A: Array> Iterator for Iter<A>
-where
-    A::Item: Default,
-{
+impl<A: Array> Iter<A> {
+    pub fn as_slice(&self) -> &[A::Item] {
+        match &self.0 {
+            IterRepr::Inline(iter) => {
+                &iter.array.as_slice()[(iter.pos as usize)..(iter.len as usize)]
+            }
+            IterRepr::Heap(iter) => iter.as_slice(),
+        }
+    }
+
+    /// Give back the elements not yet yielded as a new vector, keeping the representation.
+    pub fn into_vekk(self) -> Vekk<A>
+    where
+        A::Item: Default,
+    {
+        match self.0 {
+            IterRepr::Inline(iter) => iter.collect(),
+            IterRepr::Heap(iter) => Vekk::from_repr(Repr::Heap(iter.collect())),
+        }
+    }
+}
+
+impl<A: Array> AsRef<[A::Item]> for Iter<A> {
+    fn as_ref(&self) -> &[A::Item] {
+        self.as_slice()
+    }
+}
+
+impl<A: Array> Iterator for Iter<A> {
     type Item = A::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -30,61 +57,203 @@ where
             IterRepr::Heap(heap) => heap.size_hint(),
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterRepr::Inline(iter) => iter.nth(n),
+            IterRepr::Heap(iter) => iter.nth(n),
+        }
+    }
+
+    fn count(self) -> usize {
+        match self.0 {
+            IterRepr::Inline(iter) => iter.count(),
+            IterRepr::Heap(iter) => iter.len(),
+        }
+    }
+
+    fn last(self) -> Option<Self::Item> {
+        match self.0 {
+            IterRepr::Inline(iter) => iter.last(),
+            IterRepr::Heap(mut iter) => iter.next_back(),
+        }
+    }
+
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        match self.0 {
+            IterRepr::Inline(iter) => iter.fold(init, f),
+            IterRepr::Heap(iter) => iter.fold(init, f),
+        }
+    }
+}
+
+// SAFETY: Both representations report their exact remaining length
+#[cfg(feature = "unstable")]
+unsafe impl<A: Array> core::iter::TrustedLen for Iter<A> {}
+
+pub struct IterMut<'a, A: Array>(IterMutRepr<'a, A>);
+
+enum IterMutRepr<'a, A: Array> {
+    Inline(core::slice::IterMut<'a, A::Item>),
+    Heap(core::slice::IterMut<'a, A::Item>),
+}
+
+impl<'a, A: Array> Iterator for IterMut<'a, A> {
+    type Item = &'a mut A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterMutRepr::Inline(iter) => iter.next(),
+            IterMutRepr::Heap(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            IterMutRepr::Inline(iter) => iter.size_hint(),
+            IterMutRepr::Heap(iter) => iter.size_hint(),
+        }
+    }
+}
+
+impl<A: Array> DoubleEndedIterator for IterMut<'_, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            IterMutRepr::Inline(iter) => iter.next_back(),
+            IterMutRepr::Heap(iter) => iter.next_back(),
+        }
+    }
 }
 
+impl<A: Array> ExactSizeIterator for IterMut<'_, A> {}
+
+impl<A: Array> FusedIterator for IterMut<'_, A> {}
+
+/// Moves elements out of the array by position, so items need neither `Default`
+/// nor any write-back. Elements from `pos` onwards, including the filler
+/// elements past `len`, are still owned and get dropped with the iterator.
+/// Positions are only meaningful because `Array` promises that its slices are
+/// the array's own storage, the same on every call.
 struct InlineIter<A: Array> {
     pos: u16,
     len: u16,
-    array: A,
+    array: ManuallyDrop<A>,
+}
+
+impl<A: Array> InlineIter<A> {
+    /// Drop the not yet yielded elements before `end` and move past them
+    fn skip_to(&mut self, end: u16) {
+        let start = self.pos;
+        self.pos = end;
+        // SAFETY: `start..end` was not moved out yet, and `pos` now excludes it from later reads and drops
+        unsafe {
+            core::ptr::drop_in_place(
+                &mut self.array.as_slice_mut()[(start as usize)..(end as usize)],
+            )
+        }
+    }
 }
 
-impl<A: Array> Iterator for InlineIter<A>
-where
-    A::Item: Default,
-{
+impl<A: Array> Iterator for InlineIter<A> {
     type Item = A::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.pos == self.len {
             None
         } else {
-            let item = core::mem::take(&mut self.array.as_slice_mut()[self.pos as usize]);
+            // SAFETY: `pos < len` was not moved out yet, and advancing `pos` excludes it from later reads and drops
+            let item = unsafe { core::ptr::read(&self.array.as_slice()[self.pos as usize]) };
             self.pos += 1;
             Some(item)
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
+        debug_assert!(self.pos <= self.len);
         let remaining = (self.len - self.pos) as usize;
         (remaining, Some(remaining))
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= (self.len - self.pos) as usize {
+            self.skip_to(self.len);
+            None
+        } else {
+            self.skip_to(self.pos + n as u16);
+            self.next()
+        }
+    }
+
+    fn count(self) -> usize {
+        (self.len - self.pos) as usize
+    }
+
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        for item in self {
+            acc = f(acc, item);
+        }
+        acc
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        if self.pos == self.len {
+            None
+        } else {
+            self.skip_to(self.len - 1);
+            self.next()
+        }
+    }
 }
 
-impl<A: Array> IntoIterator for Vekk<A>
-where
-    A::Item: Default,
-{
+impl<A: Array> Drop for InlineIter<A> {
+    fn drop(&mut self) {
+        // SAFETY: Everything before `pos` was moved out, the rest is still owned by the array
+        unsafe { core::ptr::drop_in_place(&mut self.array.as_slice_mut()[(self.pos as usize)..]) }
+    }
+}
+
+impl<A: Array> IntoIterator for Vekk<A> {
     type Item = A::Item;
     type IntoIter = Iter<A>;
 
     fn into_iter(self) -> Self::IntoIter {
         match self.repr {
-            Repr::Inline { len, array } => {
-                Iter(IterRepr::Inline(InlineIter { pos: 0, len, array }))
-            }
+            Repr::Inline { len, array } => Iter(IterRepr::Inline(InlineIter {
+                pos: 0,
+                len,
+                array: ManuallyDrop::new(array),
+            })),
             Repr::Heap(vec) => Iter(IterRepr::Heap(vec.into_iter())),
         }
     }
 }
 
-impl<'a, A: Array> IntoIterator for &'a Vekk<A>
-where
-    A::Item: Default,
-{
+impl<'a, A: Array> IntoIterator for &'a Vekk<A> {
     type Item = &'a A::Item;
     type IntoIter = <&'a [A::Item] as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.deref().into_iter()
+        self.deref().iter()
+    }
+}
+
+impl<'a, A: Array> IntoIterator for &'a mut Vekk<A> {
+    type Item = &'a mut A::Item;
+    type IntoIter = IterMut<'a, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match &mut self.repr {
+            Repr::Inline { len, array } => IterMut(IterMutRepr::Inline(
+                array.as_slice_mut()[..(*len as usize)].iter_mut(),
+            )),
+            Repr::Heap(vec) => IterMut(IterMutRepr::Heap(vec.iter_mut())),
+        }
     }
 }
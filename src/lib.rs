@@ -1,10 +1,34 @@
-use std::ops::{Deref, DerefMut};
+#![no_std]
+#![cfg_attr(feature = "unstable", feature(trusted_len))]
+
+extern crate alloc;
+
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{Bound, Deref, DerefMut, RangeBounds};
 
 use thin_vec::ThinVec;
 
+#[cfg(feature = "compat")]
+pub mod compat;
+pub mod deque;
+#[cfg(feature = "instrument")]
+pub mod instrument;
 pub mod iter;
+#[cfg(feature = "serde")]
+mod serde;
 
-pub trait Array: IntoIterator {
+/// Backing storage for the inline representation.
+///
+/// # Safety
+///
+/// `as_slice` and `as_slice_mut` must return the `CAPACITY` elements stored in the array itself,
+/// and the same elements on every call. The inline iterator moves elements out of that storage by
+/// pointer and drops the rest in place, so any other slice would be read or dropped twice.
+pub unsafe trait Array: IntoIterator {
     const CAPACITY: usize;
 
     fn default() -> Self;
@@ -12,7 +36,8 @@ pub trait Array: IntoIterator {
     fn as_slice_mut(&mut self) -> &mut [Self::Item];
 }
 
-impl<T: Default, const N: usize> Array for [T; N] {
+// SAFETY: Both slices are the array itself
+unsafe impl<T: Default, const N: usize> Array for [T; N] {
     const CAPACITY: usize = N;
 
     #[inline]
@@ -33,6 +58,9 @@ impl<T: Default, const N: usize> Array for [T; N] {
 
 pub struct Vekk<A: Array> {
     repr: Repr<A>,
+    /// Capacities after the initial inline one, so inline vectors never allocate for it
+    #[cfg(all(feature = "capacity-history", debug_assertions))]
+    history: Vec<usize>,
 }
 
 enum Repr<A: Array> {
@@ -40,7 +68,72 @@ enum Repr<A: Array> {
     Heap(ThinVec<A::Item>),
 }
 
+enum Decision {
+    Keep,
+    Remove,
+}
+
+impl Decision {
+    fn keep_if(keep: bool) -> Self {
+        if keep {
+            Self::Keep
+        } else {
+            Self::Remove
+        }
+    }
+}
+
+/// Whether a shrinking operation moves a heap vector back inline.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SpillPolicy {
+    /// Move back inline whenever the result fits, even if nothing was removed.
+    Eager,
+    /// Stay on the heap.
+    #[default]
+    Never,
+    /// Move back inline when the operation removed elements and the result fits.
+    OnShrink,
+}
+
 impl<A: Array> Vekk<A> {
+    #[inline]
+    fn from_repr(repr: Repr<A>) -> Self {
+        #[allow(unused_mut)]
+        let mut vekk = Self {
+            repr,
+            #[cfg(all(feature = "capacity-history", debug_assertions))]
+            history: Vec::new(),
+        };
+        vekk.record_capacity();
+        vekk
+    }
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity <= Self::inline_capacity() {
+            Self::default()
+        } else {
+            Self::from_repr(Repr::Heap(ThinVec::with_capacity(capacity)))
+        }
+    }
+
+    /// The capacity a vector ends up with after pushing `expected_len` elements one by one:
+    /// the inline capacity if they fit, otherwise the spill capacity doubled until it's enough.
+    pub fn recommended_capacity(expected_len: usize) -> usize {
+        if expected_len <= Self::inline_capacity() {
+            return Self::inline_capacity();
+        }
+
+        let mut capacity = Self::spill_capacity(Self::inline_capacity() + 1);
+        while capacity < expected_len {
+            capacity = capacity.saturating_mul(2);
+        }
+        capacity
+    }
+
     pub fn len(&self) -> usize {
         match &self.repr {
             Repr::Inline { len, .. } => *len as usize,
@@ -48,6 +141,35 @@ impl<A: Array> Vekk<A> {
         }
     }
 
+    pub fn capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { .. } => Self::inline_capacity(),
+            Repr::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    pub fn remaining_inline_capacity(&self) -> usize {
+        match &self.repr {
+            Repr::Inline { len, .. } => Self::inline_capacity() - *len as usize,
+            Repr::Heap(_) => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The whole backing array, if the vector is stored inline.
+    ///
+    /// Only the first `len()` elements are part of the vector.
+    /// The contents of the rest of the array are unspecified.
+    pub fn inline_array(&self) -> Option<&A> {
+        match &self.repr {
+            Repr::Inline { array, .. } => Some(array),
+            Repr::Heap(_) => None,
+        }
+    }
+
     pub fn as_slice(&self) -> &[A::Item] {
         self.deref()
     }
@@ -56,6 +178,164 @@ impl<A: Array> Vekk<A> {
         self.deref_mut()
     }
 
+    pub fn split_at_mut(&mut self, mid: usize) -> (&mut [A::Item], &mut [A::Item]) {
+        self.check_position(mid);
+        self.as_mut_slice().split_at_mut(mid)
+    }
+
+    #[cfg(feature = "bytemuck")]
+    pub fn as_byte_slice(&self) -> &[u8]
+    where
+        A::Item: bytemuck::Pod,
+    {
+        bytemuck::cast_slice(self.as_slice())
+    }
+
+    /// A pointer to the first element, for passing to FFI.
+    ///
+    /// Never null, also when empty: an empty inline vector points into its
+    /// array, an empty heap vector gives a dangling but aligned pointer. This
+    /// makes it safe for C APIs that reject null even for zero-length buffers.
+    pub fn as_ptr(&self) -> *const A::Item {
+        self.as_slice().as_ptr()
+    }
+
+    /// The mutable counterpart of `as_ptr`, with the same non-null guarantee.
+    pub fn as_mut_ptr(&mut self) -> *mut A::Item {
+        self.as_mut_slice().as_mut_ptr()
+    }
+
+    pub fn as_slice_of_cells(&mut self) -> &[core::cell::Cell<A::Item>] {
+        core::cell::Cell::from_mut(self.as_mut_slice()).as_slice_of_cells()
+    }
+
+    /// Iterate over the elements together with their indices.
+    ///
+    /// ```
+    /// let v: vekk::Vekk<[char; 4]> = vec!['a', 'b'].into();
+    /// let indexed: Vec<_> = v.indexed().collect();
+    /// assert_eq!(indexed, vec![(0, &'a'), (1, &'b')]);
+    /// ```
+    pub fn indexed(&self) -> impl Iterator<Item = (usize, &A::Item)> {
+        self.as_slice().iter().enumerate()
+    }
+
+    /// Iterate over copies of the elements.
+    ///
+    /// ```
+    /// let v: vekk::Vekk<[u32; 4]> = vec![1, 2].into();
+    /// let sum: u32 = v.copied().sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn copied(&self) -> impl Iterator<Item = A::Item> + '_
+    where
+        A::Item: Copy,
+    {
+        self.as_slice().iter().copied()
+    }
+
+    /// Iterate over clones of the elements.
+    ///
+    /// ```
+    /// let v: vekk::Vekk<[String; 4]> = vec!["a".to_string(), "b".to_string()].into();
+    /// let cloned: Vec<String> = v.cloned().collect();
+    /// assert_eq!(cloned, vec!["a", "b"]);
+    /// ```
+    pub fn cloned(&self) -> impl Iterator<Item = A::Item> + '_
+    where
+        A::Item: Clone,
+    {
+        self.as_slice().iter().cloned()
+    }
+
+    /// Sum the elements, consuming the vector.
+    ///
+    /// ```
+    /// let inline: vekk::Vekk<[u64; 4]> = vec![1, 2, 3].into();
+    /// assert_eq!(inline.sum::<u64>(), 6);
+    ///
+    /// let spilled: vekk::Vekk<[u64; 2]> = vec![1, 2, 3].into();
+    /// assert_eq!(spilled.sum::<u64>(), 6);
+    /// ```
+    pub fn sum<S: core::iter::Sum<A::Item>>(self) -> S {
+        self.into_iter().sum()
+    }
+
+    /// Multiply the elements, consuming the vector.
+    ///
+    /// ```
+    /// let inline: vekk::Vekk<[u64; 4]> = vec![2, 3, 4].into();
+    /// assert_eq!(inline.product::<u64>(), 24);
+    ///
+    /// let spilled: vekk::Vekk<[u64; 2]> = vec![2, 3, 4].into();
+    /// assert_eq!(spilled.product::<u64>(), 24);
+    /// ```
+    pub fn product<P: core::iter::Product<A::Item>>(self) -> P {
+        self.into_iter().product()
+    }
+
+    pub fn max(&self) -> Option<&A::Item>
+    where
+        A::Item: Ord,
+    {
+        self.iter().max()
+    }
+
+    pub fn min(&self) -> Option<&A::Item>
+    where
+        A::Item: Ord,
+    {
+        self.iter().min()
+    }
+
+    pub fn get_disjoint_mut<const K: usize>(
+        &mut self,
+        indices: [usize; K],
+    ) -> Option<[&mut A::Item; K]> {
+        self.as_mut_slice().get_disjoint_mut(indices).ok()
+    }
+
+    pub fn iter_mut(&mut self) -> iter::IterMut<'_, A> {
+        self.into_iter()
+    }
+
+    pub fn as_chunks<const N: usize>(&self) -> (&[[A::Item; N]], &[A::Item]) {
+        self.as_slice().as_chunks()
+    }
+
+    pub fn as_rchunks<const N: usize>(&self) -> (&[A::Item], &[[A::Item; N]]) {
+        self.as_slice().as_rchunks()
+    }
+
+    pub fn rchunks(&self, chunk_size: usize) -> core::slice::RChunks<'_, A::Item> {
+        self.as_slice().rchunks(chunk_size)
+    }
+
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> core::slice::RChunksMut<'_, A::Item> {
+        self.as_mut_slice().rchunks_mut(chunk_size)
+    }
+
+    pub fn rsplit<F>(&self, pred: F) -> core::slice::RSplit<'_, A::Item, F>
+    where
+        F: FnMut(&A::Item) -> bool,
+    {
+        self.as_slice().rsplit(pred)
+    }
+
+    pub fn rsplitn<F>(&self, n: usize, pred: F) -> core::slice::RSplitN<'_, A::Item, F>
+    where
+        F: FnMut(&A::Item) -> bool,
+    {
+        self.as_slice().rsplitn(n, pred)
+    }
+
+    pub fn chunk_by<F>(&self, pred: F) -> core::slice::ChunkBy<'_, A::Item, F>
+    where
+        F: FnMut(&A::Item, &A::Item) -> bool,
+    {
+        self.as_slice().chunk_by(pred)
+    }
+
     pub fn push(&mut self, item: A::Item)
     where
         A::Item: Default,
@@ -63,151 +343,1147 @@ impl<A: Array> Vekk<A> {
         self.push_inner(item);
     }
 
-    pub fn extend(&mut self, iter: impl IntoIterator<Item = A::Item>)
+    /// Push `item` and return a mutable reference to it in its new place.
+    pub fn push_and_get_mut(&mut self, item: A::Item) -> &mut A::Item
     where
         A::Item: Default,
     {
-        for item in iter {
+        self.push_inner(item);
+        let last = self.len() - 1;
+        &mut self.as_mut_slice()[last]
+    }
+
+    pub fn push_unique(&mut self, item: A::Item) -> bool
+    where
+        A::Item: PartialEq + Default,
+    {
+        if self.contains(&item) {
+            false
+        } else {
             self.push_inner(item);
+            true
         }
     }
 
-    pub fn pop(&mut self) -> Option<A::Item>
+    pub fn push_rolling(&mut self, max: usize, item: A::Item)
     where
         A::Item: Default,
     {
-        match &mut self.repr {
-            Repr::Inline { len, array } => {
-                if *len > 0 {
-                    let item = core::mem::take(&mut array.as_slice_mut()[(*len - 1) as usize]);
-                    *len -= 1;
-                    Some(item)
-                } else {
-                    None
-                }
+        let len = self.len();
+        if len >= max {
+            self.drain_front(len + 1 - max);
+        }
+        if max > 0 {
+            self.push_inner(item);
+        }
+    }
+
+    /// Move the elements of an array of any length in, without requiring `Default` items.
+    pub fn from_array<const M: usize>(items: [A::Item; M]) -> Self {
+        if M > Self::inline_capacity() {
+            return Self::from_repr(Repr::Heap(ThinVec::from_iter(items)));
+        }
+
+        let mut array = A::default();
+        for (slot, item) in array.as_slice_mut().iter_mut().zip(items) {
+            *slot = item;
+        }
+        Self::from_repr(Repr::Inline {
+            len: M as u16,
+            array,
+        })
+    }
+
+    /// Clone a slice into the inline array, failing instead of allocating if it doesn't fit.
+    #[allow(clippy::result_unit_err)]
+    pub fn try_from_slice_inline(slice: &[A::Item]) -> Result<Self, ()>
+    where
+        A::Item: Clone,
+    {
+        if slice.len() > Self::inline_capacity() {
+            return Err(());
+        }
+
+        let mut array = A::default();
+        array.as_slice_mut()[..slice.len()].clone_from_slice(slice);
+        Ok(Self::from_repr(Repr::Inline {
+            len: slice.len() as u16,
+            array,
+        }))
+    }
+
+    /// Collect into the inline array first, only spilling to the heap when it
+    /// actually overflows, regardless of the iterator's `size_hint`.
+    pub fn from_iter_inline_preferred(iter: impl IntoIterator<Item = A::Item>) -> Self
+    where
+        A::Item: Default,
+    {
+        let mut iter = iter.into_iter();
+        let mut array = A::default();
+        let mut len = 0;
+
+        let inline_capacity = Self::inline_capacity();
+
+        while let Some(item) = iter.next() {
+            if len == inline_capacity {
+                let heap_capacity = Self::spill_capacity(inline_capacity + 1 + iter.size_hint().0);
+                let mut vec = Self::thinvec_from_array(&mut array, len, heap_capacity);
+
+                vec.push(item);
+                vec.extend(iter);
+
+                return Self::from_repr(Repr::Heap(vec));
             }
-            Repr::Heap(vec) => {
-                // Currently does not switch back to inline representation
-                vec.pop()
+
+            array.as_slice_mut()[len] = item;
+            len += 1;
+        }
+
+        Self::from_repr(Repr::Inline {
+            len: len as u16,
+            array,
+        })
+    }
+
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = A::Item>)
+    where
+        A::Item: Default,
+    {
+        let mut iter = iter.into_iter();
+        while matches!(self.repr, Repr::Inline { .. }) {
+            match iter.next() {
+                Some(item) => self.push_inner(item),
+                None => return,
             }
         }
+
+        // Once spilled, skip the per-element representation check
+        if let Repr::Heap(vec) = &mut self.repr {
+            vec.extend(iter);
+        }
+        self.record_capacity();
     }
 
-    pub fn insert(&mut self, index: usize, element: A::Item)
+    /// Append the items of an iterator that yields exactly `count` items, reserving once.
+    /// At most `count` items are taken from the iterator.
+    ///
+    /// Debug builds panic if the iterator yields a different number of items. To detect extra
+    /// items they pull one more from the iterator, so its state afterwards differs from release builds.
+    pub fn append_exact(&mut self, count: usize, mut iter: impl Iterator<Item = A::Item>)
     where
         A::Item: Default,
     {
+        let start = self.len();
+        self.reserve(count);
+
         match &mut self.repr {
             Repr::Inline { len, array } => {
-                if (*len as usize) == Self::inline_capacity() {
-                    let mut vec = Self::thinvec_from_array(array, Self::inline_capacity() + 1);
-                    vec.insert(index, element);
-                    self.repr = Repr::Heap(vec);
-                } else {
-                    let slice = array.as_slice_mut();
-                    for idx in index..(*len as usize) {
-                        slice.swap(idx, idx + 1);
-                    }
-                    slice[index] = element;
+                for (slot, item) in array.as_slice_mut()[start..(start + count)]
+                    .iter_mut()
+                    .zip(iter.by_ref())
+                {
+                    *slot = item;
                     *len += 1;
                 }
             }
-            Repr::Heap(vec) => {
-                vec.insert(index, element);
+            Repr::Heap(vec) => vec.extend(iter.by_ref().take(count)),
+        }
+
+        if cfg!(debug_assertions) {
+            let exact = self.len() - start == count && iter.next().is_none();
+            assert!(
+                exact,
+                "append_exact: iterator did not yield exactly {count} items"
+            );
+        }
+    }
+
+    /// Move the elements of all `others` onto the end, growing at most once for their total length.
+    pub fn extend_vekks(&mut self, others: impl IntoIterator<Item = Vekk<A>>)
+    where
+        A::Item: Default,
+    {
+        // Collect first, the total length is only known once every source has been seen.
+        // Up to 8 sources are held inline, so typical merges don't allocate for it.
+        let mut others = others
+            .into_iter()
+            .collect::<Vekk<[Vekk<A>; 8]>>()
+            .into_iter();
+        if self.is_empty() {
+            if let Some(first) = others.next() {
+                self.set_repr(first.repr);
             }
         }
+
+        self.reserve(others.as_slice().iter().map(Vekk::len).sum());
+        for other in others {
+            self.extend(other);
+        }
     }
 
-    fn inline_capacity() -> usize {
-        core::cmp::min(A::CAPACITY, u16::MAX as usize)
+    pub fn extend_from_slice(&mut self, other: &[A::Item])
+    where
+        A::Item: Clone + Default,
+    {
+        self.reserve(other.len());
+        for item in other {
+            self.push_inner(item.clone());
+        }
     }
 
-    #[inline]
-    fn thinvec_from_array(array: &mut A, capacity: usize) -> ThinVec<A::Item>
+    pub fn extend_from_within(&mut self, range: impl RangeBounds<usize>)
+    where
+        A::Item: Clone + Default,
+    {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1),
+            Bound::Excluded(&end) => Some(end),
+            Bound::Unbounded => Some(len),
+        };
+        let (Some(start), Some(end)) = (start, end) else {
+            panic!(
+                "range {:?} out of bounds for Vekk of length {len}",
+                (range.start_bound(), range.end_bound())
+            );
+        };
+        assert!(
+            start <= end && end <= len,
+            "range {start}..{end} out of bounds for Vekk of length {len}"
+        );
+
+        // Spill up front, so the source range stays put while cloning from it
+        self.reserve(end - start);
+        for index in start..end {
+            let item = self.as_slice()[index].clone();
+            self.push_inner(item);
+        }
+    }
+
+    pub fn reserve(&mut self, additional: usize)
     where
         A::Item: Default,
     {
-        let mut vec = ThinVec::with_capacity(capacity);
-        for item in array.as_slice_mut() {
-            let item = core::mem::take(item);
-            vec.push(item);
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                let required = (*len as usize)
+                    .checked_add(additional)
+                    .expect("capacity overflow");
+                if required > Self::inline_capacity() {
+                    let vec = Self::thinvec_from_array(
+                        array,
+                        *len as usize,
+                        Self::spill_capacity(required),
+                    );
+                    self.set_repr(Repr::Heap(vec));
+                }
+            }
+            Repr::Heap(vec) => vec.reserve(additional),
         }
-        vec
+        self.record_capacity();
     }
 
-    #[inline]
-    pub fn push_inner(&mut self, item: A::Item)
+    /// Like `reserve`, but a spill or reallocation asks for exactly `len + additional`
+    /// instead of growing amortized.
+    pub fn reserve_exact(&mut self, additional: usize)
     where
         A::Item: Default,
     {
         match &mut self.repr {
             Repr::Inline { len, array } => {
-                if *len as usize == Self::inline_capacity() {
-                    let mut vec = Self::thinvec_from_array(array, Self::inline_capacity() + 1);
-                    vec.push(item);
-                    self.repr = Repr::Heap(vec);
+                let required = (*len as usize)
+                    .checked_add(additional)
+                    .expect("capacity overflow");
+                if required > Self::inline_capacity() {
+                    let vec = Self::thinvec_from_array(array, *len as usize, required);
+                    self.set_repr(Repr::Heap(vec));
+                }
+            }
+            Repr::Heap(vec) => vec.reserve_exact(additional),
+        }
+        self.record_capacity();
+    }
+
+    pub fn pop(&mut self) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                if *len > 0 {
+                    let item = core::mem::take(&mut array.as_slice_mut()[(*len - 1) as usize]);
+                    *len -= 1;
+                    Some(item)
                 } else {
-                    array.as_slice_mut()[*len as usize] = item;
-                    *len += 1;
+                    None
                 }
             }
             Repr::Heap(vec) => {
-                vec.push(item);
+                // Does not switch back to inline representation, see `compact`
+                vec.pop()
             }
         }
     }
-}
-
-impl<A: Array> core::ops::Deref for Vekk<A> {
-    type Target = [A::Item];
 
-    fn deref(&self) -> &Self::Target {
-        match &self.repr {
-            Repr::Inline { len, array } => &array.as_slice()[..(*len as usize)],
-            Repr::Heap(vec) => vec.as_slice(),
-        }
+    pub fn pop_with_policy(&mut self, policy: SpillPolicy) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        let item = self.pop();
+        self.apply_spill_policy(policy, item.is_some());
+        item
     }
-}
 
-impl<A: Array> core::ops::DerefMut for Vekk<A> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        match &mut self.repr {
-            Repr::Inline { len, array } => &mut array.as_slice_mut()[..(*len as usize)],
-            Repr::Heap(vec) => vec.as_mut_slice(),
+    pub fn pop_if(&mut self, pred: impl FnOnce(&mut A::Item) -> bool) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        if pred(self.last_mut()?) {
+            self.pop()
+        } else {
+            None
         }
     }
-}
 
-impl<A: Array> Default for Vekk<A> {
-    fn default() -> Self {
-        Self {
-            repr: Repr::Inline {
-                len: 0,
-                array: A::default(),
-            },
+    pub fn pop_front(&mut self) -> Option<A::Item>
+    where
+        A::Item: Default,
+    {
+        if self.is_empty() {
+            return None;
         }
+
+        let item = self.remove(0);
+        self.compact();
+        Some(item)
     }
-}
 
-impl<A: Array> Clone for Vekk<A>
-where
-    A: Clone,
-    A::Item: Clone,
-{
-    fn clone(&self) -> Self {
-        Self {
-            repr: self.repr.clone(),
-        }
+    /// Split off the first element by value, moving the rest back inline if it fits.
+    pub fn split_first_owned(mut self) -> Option<(A::Item, Self)>
+    where
+        A::Item: Default,
+    {
+        let first = self.pop_front()?;
+        Some((first, self))
     }
-}
 
-impl<A: Array> Clone for Repr<A>
+    /// Remove the first `n` elements, dropping them front-to-back.
+    pub fn drain_front(&mut self, n: usize)
+    where
+        A::Item: Default,
+    {
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                let n = core::cmp::min(n, *len as usize);
+                let slice = &mut array.as_slice_mut()[..(*len as usize)];
+                for item in &mut slice[..n] {
+                    Self::take_and_drop(item);
+                }
+                slice.rotate_left(n);
+                *len -= n as u16;
+            }
+            Repr::Heap(vec) => {
+                let n = core::cmp::min(n, vec.len());
+                vec.drain(..n);
+                self.compact();
+            }
+        }
+    }
+
+    /// Shorten to `new_len`, dropping the removed elements front-to-back like `Vec::truncate`.
+    ///
+    /// Only the length changes, a heap vector keeps its allocation even if the rest would fit inline.
+    /// This is the one to use in loops that refill the vector, use `truncate_and_shrink` to reclaim memory.
+    pub fn truncate(&mut self, new_len: usize)
+    where
+        A::Item: Default,
+    {
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                if new_len < *len as usize {
+                    for item in &mut array.as_slice_mut()[new_len..(*len as usize)] {
+                        Self::take_and_drop(item);
+                    }
+                    *len = new_len as u16;
+                }
+            }
+            Repr::Heap(vec) => {
+                // `ThinVec::truncate` drops back-to-front, so drop the tail as one slice like `Vec` does
+                if new_len < vec.len() {
+                    // Borrow the tail before shortening, a pointer taken afterwards only covers the new length
+                    let tail: *mut [A::Item] = &mut vec[new_len..];
+                    // SAFETY: The tail is initialized, and is no longer owned by the vector once its length excludes it
+                    unsafe {
+                        vec.set_len(new_len);
+                        core::ptr::drop_in_place(tail);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Shorten to `new_len` and free what is no longer needed: moves back inline if the rest fits,
+    /// otherwise shrinks the heap allocation to the length. Costs a reallocation whenever it shrinks.
+    pub fn truncate_and_shrink(&mut self, new_len: usize)
+    where
+        A::Item: Default,
+    {
+        self.truncate(new_len);
+        self.shrink_to_fit();
+    }
+
+    /// Shorten to `new_len`, keeping the allocation. This is what `truncate` does now.
+    #[deprecated(note = "`truncate` keeps the allocation, use it instead")]
+    pub fn truncate_keep_alloc(&mut self, new_len: usize)
+    where
+        A::Item: Default,
+    {
+        self.truncate(new_len);
+    }
+
+    pub fn truncate_with_policy(&mut self, new_len: usize, policy: SpillPolicy)
+    where
+        A::Item: Default,
+    {
+        let old_len = self.len();
+        self.truncate(new_len);
+        self.apply_spill_policy(policy, self.len() < old_len);
+    }
+
+    /// Move the elements from `at` onwards into a new vector, which may have a different inline capacity.
+    pub fn split_off_into<B: Array<Item = A::Item>>(&mut self, at: usize) -> Vekk<B>
+    where
+        A::Item: Default,
+    {
+        self.check_position(at);
+
+        let tail = self.as_mut_slice()[at..]
+            .iter_mut()
+            .map(core::mem::take)
+            .collect();
+        self.truncate(at);
+        tail
+    }
+
+    /// Set the length without dropping or initializing any elements.
+    ///
+    /// Panics if `new_len` exceeds the capacity of the current representation.
+    ///
+    /// # Safety
+    ///
+    /// On the heap, the elements up to `new_len` must be initialized, as for `Vec::set_len`.
+    /// Inline elements are always initialized, shrinking just leaves them behind in the array.
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        assert!(
+            new_len <= self.capacity(),
+            "set_len {new_len} exceeds capacity {}",
+            self.capacity()
+        );
+
+        match &mut self.repr {
+            Repr::Inline { len, .. } => *len = new_len as u16,
+            Repr::Heap(vec) => unsafe { vec.set_len(new_len) },
+        }
+    }
+
+    /// Resize to `new_len`, filling with clones of `value`.
+    /// Shrinking moves back inline if the remaining elements fit.
+    pub fn resize(&mut self, new_len: usize, value: A::Item)
+    where
+        A::Item: Clone + Default,
+    {
+        let len = self.len();
+        if new_len > len {
+            self.reserve(new_len - len);
+            for _ in (len + 1)..new_len {
+                self.push_inner(value.clone());
+            }
+            self.push_inner(value);
+        } else {
+            self.truncate_with_policy(new_len, SpillPolicy::Eager);
+        }
+    }
+
+    /// Resize to `new_len`, filling with values returned by `f`.
+    /// Shrinking moves back inline if the remaining elements fit.
+    pub fn resize_with(&mut self, new_len: usize, mut f: impl FnMut() -> A::Item)
+    where
+        A::Item: Default,
+    {
+        let len = self.len();
+        if new_len > len {
+            self.reserve(new_len - len);
+            for _ in len..new_len {
+                self.push_inner(f());
+            }
+        } else {
+            self.truncate_with_policy(new_len, SpillPolicy::Eager);
+        }
+    }
+
+    /// Keep the elements for which `f` holds. The others are dropped as they are visited,
+    /// which is front-to-back like `Vec::retain`.
+    pub fn retain(&mut self, f: impl FnMut(&A::Item) -> bool)
+    where
+        A::Item: Default,
+    {
+        self.retain_with_policy(f, SpillPolicy::Never);
+    }
+
+    pub fn retain_with_policy(&mut self, mut f: impl FnMut(&A::Item) -> bool, policy: SpillPolicy)
+    where
+        A::Item: Default,
+    {
+        // `ThinVec::retain` is faster on the heap, but drops the removed elements out of order.
+        // That can only be observed through drop glue.
+        if !core::mem::needs_drop::<A::Item>() {
+            if let Repr::Heap(vec) = &mut self.repr {
+                let len = vec.len();
+                vec.retain(f);
+                let shrank = vec.len() < len;
+                self.apply_spill_policy(policy, shrank);
+                return;
+            }
+        }
+
+        self.compact_with(policy, |item, _| Decision::keep_if(f(item)));
+    }
+
+    /// Keep the prefix of elements for which `f` holds, truncating at the first one that fails.
+    /// Moves back inline if the prefix fits.
+    pub fn retain_while(&mut self, mut f: impl FnMut(&A::Item) -> bool)
+    where
+        A::Item: Default,
+    {
+        let prefix = self.iter().position(|item| !f(item)).unwrap_or(self.len());
+        self.truncate_with_policy(prefix, SpillPolicy::Eager);
+    }
+
+    pub fn retain_indexed(&mut self, mut f: impl FnMut(usize, &A::Item) -> bool)
+    where
+        A::Item: Default,
+    {
+        let mut index = 0;
+        self.retain(|item| {
+            let keep = f(index, item);
+            index += 1;
+            keep
+        });
+    }
+
+    /// Replace each element with the result of `f`, removing it when `f` returns `None`.
+    pub fn filter_map_in_place(&mut self, mut f: impl FnMut(A::Item) -> Option<A::Item>)
+    where
+        A::Item: Default,
+    {
+        self.compact_with(SpillPolicy::Never, |item, _| {
+            match f(core::mem::take(item)) {
+                Some(mapped) => {
+                    *item = mapped;
+                    Decision::Keep
+                }
+                None => Decision::Remove,
+            }
+        });
+    }
+
+    /// Remove consecutive duplicates, moving back inline if the result fits.
+    pub fn dedup_and_compact(&mut self)
+    where
+        A::Item: PartialEq + Default,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Sort and remove duplicates, leaving a canonical sorted-unique form.
+    /// Moves back inline if the result fits.
+    pub fn normalize(&mut self)
+    where
+        A::Item: Ord + Default,
+    {
+        self.sort_unstable();
+        self.dedup_and_compact();
+    }
+
+    pub fn dedup_by_key<K: PartialEq>(&mut self, mut key: impl FnMut(&mut A::Item) -> K)
+    where
+        A::Item: Default,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    pub fn dedup_by(&mut self, mut same_bucket: impl FnMut(&mut A::Item, &mut A::Item) -> bool)
+    where
+        A::Item: Default,
+    {
+        self.compact_with(SpillPolicy::Eager, |item, kept| match kept.last_mut() {
+            Some(last_kept) => Decision::keep_if(!same_bucket(item, last_kept)),
+            None => Decision::Keep,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        match &mut self.repr {
+            Repr::Inline { len, .. } if !core::mem::needs_drop::<A::Item>() => *len = 0,
+            _ => self.set_repr(Self::default().repr),
+        }
+    }
+
+    /// Move all elements out into the returned vector, leaving `self` empty and inline.
+    pub fn take(&mut self) -> Self {
+        core::mem::take(self)
+    }
+
+    pub fn clear_if(&mut self, pred: impl FnOnce(&[A::Item]) -> bool) {
+        if pred(self.as_slice()) {
+            self.clear();
+        }
+    }
+
+    pub fn insert(&mut self, index: usize, element: A::Item)
+    where
+        A::Item: Default,
+    {
+        self.check_position(index);
+
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                if (*len as usize) == Self::inline_capacity() {
+                    let mut vec = Self::thinvec_from_array(
+                        array,
+                        Self::inline_capacity(),
+                        Self::spill_capacity(Self::inline_capacity() + 1),
+                    );
+                    Self::thinvec_insert(&mut vec, index, element);
+                    self.set_repr(Repr::Heap(vec));
+                } else {
+                    let slice = &mut array.as_slice_mut()[..=(*len as usize)];
+                    slice[index..].rotate_right(1);
+                    slice[index] = element;
+                    *len += 1;
+                }
+            }
+            Repr::Heap(vec) => {
+                Self::thinvec_insert(vec, index, element);
+            }
+        }
+        self.record_capacity();
+    }
+
+    pub fn insert_sorted(&mut self, item: A::Item) -> usize
+    where
+        A::Item: Ord + Default,
+    {
+        let (Ok(index) | Err(index)) = self.binary_search(&item);
+        self.insert(index, item);
+        index
+    }
+
+    pub fn remove(&mut self, index: usize) -> A::Item
+    where
+        A::Item: Default,
+    {
+        self.check_index(index);
+
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                let slice = &mut array.as_slice_mut()[..(*len as usize)];
+                let item = core::mem::take(&mut slice[index]);
+                slice[index..].rotate_left(1);
+                *len -= 1;
+                item
+            }
+            Repr::Heap(vec) => vec.remove(index),
+        }
+    }
+
+    /// Remove the elements at all of `sorted_indices` in a single pass.
+    ///
+    /// The indices must be sorted in ascending order and unique, which is checked in debug builds.
+    pub fn remove_indices(&mut self, sorted_indices: &[usize])
+    where
+        A::Item: Default,
+    {
+        debug_assert!(
+            sorted_indices.windows(2).all(|pair| pair[0] < pair[1]),
+            "remove_indices: indices must be sorted and unique"
+        );
+        if let Some(&last) = sorted_indices.last() {
+            self.check_index(last);
+        }
+
+        let mut removed = sorted_indices.iter().peekable();
+        let mut index = 0;
+        self.compact_with(SpillPolicy::Never, |_, _| {
+            let decision = Decision::keep_if(removed.next_if_eq(&&index).is_none());
+            index += 1;
+            decision
+        });
+    }
+
+    pub fn swap_remove(&mut self, index: usize) -> A::Item
+    where
+        A::Item: Default,
+    {
+        self.check_index(index);
+
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                let slice = &mut array.as_slice_mut()[..(*len as usize)];
+                let last = slice.len() - 1;
+                slice.swap(index, last);
+                *len -= 1;
+                core::mem::take(&mut slice[last])
+            }
+            Repr::Heap(vec) => vec.swap_remove(index),
+        }
+    }
+
+    pub fn swap_remove_with_policy(&mut self, index: usize, policy: SpillPolicy) -> A::Item
+    where
+        A::Item: Default,
+    {
+        let item = self.swap_remove(index);
+        self.apply_spill_policy(policy, true);
+        item
+    }
+
+    pub fn remove_with_policy(&mut self, index: usize, policy: SpillPolicy) -> A::Item
+    where
+        A::Item: Default,
+    {
+        let item = self.remove(index);
+        self.apply_spill_policy(policy, true);
+        item
+    }
+
+    /// Consume the vector `N` elements at a time. A remainder shorter than `N` is dropped.
+    pub fn into_chunks<const N: usize>(self) -> impl Iterator<Item = [A::Item; N]> {
+        assert!(N != 0, "chunk size must be non-zero");
+
+        let mut iter = self.into_iter();
+        core::iter::from_fn(move || {
+            if iter.size_hint().0 < N {
+                return None;
+            }
+            Some(core::array::from_fn(|_| iter.next().unwrap()))
+        })
+    }
+
+    /// Consume the vector `N` elements at a time, returning the remainder shorter than `N` separately.
+    pub fn into_chunks_with_remainder<const N: usize>(
+        mut self,
+    ) -> (impl Iterator<Item = [A::Item; N]>, Vekk<A>)
+    where
+        A::Item: Default,
+    {
+        assert!(N != 0, "chunk size must be non-zero");
+
+        let remainder = self.split_off_into(self.len() - self.len() % N);
+        (self.into_chunks(), remainder)
+    }
+
+    pub fn into_vec(self) -> Vec<A::Item>
+    where
+        A::Item: Default,
+    {
+        match self.repr {
+            Repr::Inline { .. } => self.into_iter().collect(),
+            Repr::Heap(vec) => vec.into(),
+        }
+    }
+
+    pub fn clone_into_vec(&self) -> Vec<A::Item>
+    where
+        A::Item: Clone,
+    {
+        self.as_slice().to_vec()
+    }
+
+    /// Collect runs of equal consecutive elements as `(value, count)` pairs.
+    ///
+    /// ```
+    /// let v: vekk::Vekk<[u8; 8]> = vec![1, 1, 2].into();
+    /// let runs: vekk::Vekk<[(u8, usize); 4]> = v.run_length_encode();
+    /// assert_eq!(runs.as_slice(), &[(1, 2), (2, 1)]);
+    /// ```
+    pub fn run_length_encode<B: Array<Item = (A::Item, usize)>>(&self) -> Vekk<B>
+    where
+        A::Item: Clone + PartialEq + Default,
+    {
+        self.as_slice()
+            .chunk_by(|a, b| a == b)
+            .map(|run| (run[0].clone(), run.len()))
+            .collect()
+    }
+
+    pub fn into_owned_slice(self) -> Box<[A::Item]>
+    where
+        A::Item: Default,
+    {
+        self.into_vec().into_boxed_slice()
+    }
+
+    pub fn try_into_inline_array(self) -> Result<A, Self> {
+        if self.len() != A::CAPACITY {
+            return Err(self);
+        }
+
+        match self.repr {
+            Repr::Inline { array, .. } => Ok(array),
+            Repr::Heap(vec) => {
+                let mut array = A::default();
+                for (slot, item) in array.as_slice_mut().iter_mut().zip(vec) {
+                    *slot = item;
+                }
+                Ok(array)
+            }
+        }
+    }
+
+    /// Move a heap vector back into the inline array if its elements fit,
+    /// freeing the allocation. This is the way back from any spill, however
+    /// large the heap capacity has grown.
+    pub fn compact(&mut self) {
+        if let Repr::Heap(vec) = &mut self.repr {
+            let len = vec.len();
+            if len <= Self::inline_capacity() {
+                let mut array = A::default();
+                for (slot, item) in array.as_slice_mut().iter_mut().zip(core::mem::take(vec)) {
+                    *slot = item;
+                }
+                self.set_repr(Repr::Inline {
+                    len: len as u16,
+                    array,
+                });
+            }
+        }
+    }
+
+    /// Replace the representation, reporting inline/heap transitions to the instrumentation hook.
+    #[inline]
+    fn set_repr(&mut self, repr: Repr<A>) {
+        #[cfg(feature = "instrument")]
+        let was_heap = matches!(self.repr, Repr::Heap(_));
+
+        self.repr = repr;
+
+        #[cfg(feature = "instrument")]
+        match (was_heap, &self.repr) {
+            (false, Repr::Heap(vec)) => instrument::report::<A>(
+                instrument::Transition::Spill,
+                vec.len(),
+                Self::inline_capacity(),
+            ),
+            (true, Repr::Inline { len, .. }) => instrument::report::<A>(
+                instrument::Transition::Compact,
+                *len as usize,
+                Self::inline_capacity(),
+            ),
+            _ => {}
+        }
+
+        self.record_capacity();
+    }
+
+    #[inline]
+    fn record_capacity(&mut self) {
+        #[cfg(all(feature = "capacity-history", debug_assertions))]
+        {
+            let capacity = self.capacity();
+            let last = self
+                .history
+                .last()
+                .copied()
+                .unwrap_or(Self::inline_capacity());
+            if capacity != last {
+                self.history.push(capacity);
+            }
+        }
+    }
+
+    /// The sequence of distinct capacities this vector has had, starting with
+    /// the inline capacity, e.g. `[4, 8, 16]` after spilling and growing once.
+    ///
+    /// Recorded when pushing, inserting, extending, reserving and changing
+    /// representation. A diagnostic for picking an inline size that avoids
+    /// allocations for a workload, only available in debug builds.
+    #[cfg(all(feature = "capacity-history", debug_assertions))]
+    pub fn capacity_history(&self) -> impl Iterator<Item = usize> + '_ {
+        core::iter::once(Self::inline_capacity()).chain(self.history.iter().copied())
+    }
+
+    /// Move back inline if the elements fit, otherwise shrink the heap allocation to the length.
+    pub fn shrink_to_fit(&mut self)
+    where
+        A::Item: Default,
+    {
+        self.compact();
+        if let Repr::Heap(vec) = &mut self.repr {
+            vec.shrink_to_fit();
+        }
+        self.record_capacity();
+    }
+
+    /// Panic unless `index` refers to an element.
+    #[inline]
+    #[track_caller]
+    fn check_index(&self, index: usize) {
+        if index >= self.len() {
+            index_out_of_bounds(index, self.len());
+        }
+    }
+
+    /// Panic unless `position` is a valid insertion or split point, which may be one past the end.
+    #[inline]
+    #[track_caller]
+    fn check_position(&self, position: usize) {
+        if position > self.len() {
+            index_out_of_bounds(position, self.len());
+        }
+    }
+
+    fn apply_spill_policy(&mut self, policy: SpillPolicy, shrank: bool)
+    where
+        A::Item: Default,
+    {
+        match policy {
+            SpillPolicy::Eager => self.compact(),
+            SpillPolicy::OnShrink if shrank => self.compact(),
+            SpillPolicy::OnShrink | SpillPolicy::Never => {}
+        }
+    }
+
+    fn inline_capacity() -> usize {
+        core::cmp::min(A::CAPACITY, u16::MAX as usize)
+    }
+
+    /// Heap capacity to allocate when spilling with `required` elements.
+    /// Grows to at least double the inline capacity, so repeated small
+    /// reservations don't reallocate on every step.
+    fn spill_capacity(required: usize) -> usize {
+        core::cmp::max(required, 2 * Self::inline_capacity())
+    }
+
+    #[inline]
+    fn thinvec_from_array(array: &mut A, len: usize, capacity: usize) -> ThinVec<A::Item>
+    where
+        A::Item: Default,
+    {
+        let mut vec = ThinVec::with_capacity(capacity);
+        for item in &mut array.as_slice_mut()[..len] {
+            let item = core::mem::take(item);
+            vec.push(item);
+        }
+        vec
+    }
+
+    /// The single compaction pass behind `retain`, `dedup_by`, `filter_map_in_place` and
+    /// `remove_indices`, for both representations.
+    ///
+    /// `decide` sees each element front to back, along with the elements kept so far.
+    /// Removed elements are dropped right away, in order. The spill policy is applied once at the end.
+    fn compact_with(
+        &mut self,
+        policy: SpillPolicy,
+        mut decide: impl FnMut(&mut A::Item, &mut [A::Item]) -> Decision,
+    ) where
+        A::Item: Default,
+    {
+        let slice = self.as_mut_slice();
+        let len = slice.len();
+        let mut write = 0;
+        for read in 0..len {
+            let (kept, rest) = slice.split_at_mut(read);
+            match decide(&mut rest[0], &mut kept[..write]) {
+                Decision::Keep => {
+                    if read != write {
+                        slice.swap(read, write);
+                    }
+                    write += 1;
+                }
+                Decision::Remove => Self::take_and_drop(&mut slice[read]),
+            }
+        }
+        self.truncate(write);
+        self.apply_spill_policy(policy, write < len);
+    }
+
+    /// Drop a removed inline element by swapping in a default value.
+    /// Elements without drop glue, like `Copy` types, are simply left behind.
+    #[inline]
+    fn take_and_drop(item: &mut A::Item)
+    where
+        A::Item: Default,
+    {
+        if core::mem::needs_drop::<A::Item>() {
+            drop(core::mem::take(item));
+        }
+    }
+
+    #[inline]
+    fn thinvec_insert(vec: &mut ThinVec<A::Item>, index: usize, element: A::Item) {
+        // `ThinVec::insert` writes a length header that zero-sized element vectors don't have.
+        // Zero-sized elements are indistinguishable by position, so appending is equivalent.
+        if core::mem::size_of::<A::Item>() == 0 {
+            assert!(index <= vec.len(), "Index out of bounds");
+            vec.push(element);
+        } else {
+            vec.insert(index, element);
+        }
+    }
+
+    #[inline]
+    pub fn push_inner(&mut self, item: A::Item)
+    where
+        A::Item: Default,
+    {
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                if *len as usize == Self::inline_capacity() {
+                    let mut vec = Self::thinvec_from_array(
+                        array,
+                        Self::inline_capacity(),
+                        Self::spill_capacity(Self::inline_capacity() + 1),
+                    );
+                    vec.push(item);
+                    self.set_repr(Repr::Heap(vec));
+                } else {
+                    array.as_slice_mut()[*len as usize] = item;
+                    *len += 1;
+                }
+            }
+            Repr::Heap(vec) => {
+                vec.push(item);
+            }
+        }
+        self.record_capacity();
+    }
+}
+
+impl<A: Array> core::ops::Deref for Vekk<A> {
+    type Target = [A::Item];
+
+    fn deref(&self) -> &Self::Target {
+        match &self.repr {
+            Repr::Inline { len, array } => &array.as_slice()[..(*len as usize)],
+            Repr::Heap(vec) => vec.as_slice(),
+        }
+    }
+}
+
+impl<A: Array> core::ops::DerefMut for Vekk<A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match &mut self.repr {
+            Repr::Inline { len, array } => &mut array.as_slice_mut()[..(*len as usize)],
+            Repr::Heap(vec) => vec.as_mut_slice(),
+        }
+    }
+}
+
+impl<A: Array> core::ops::Index<usize> for Vekk<A> {
+    type Output = A::Item;
+
+    fn index(&self, index: usize) -> &A::Item {
+        self.check_index(index);
+        &self.as_slice()[index]
+    }
+}
+
+impl<A: Array> core::ops::IndexMut<usize> for Vekk<A> {
+    fn index_mut(&mut self, index: usize) -> &mut A::Item {
+        self.check_index(index);
+        &mut self.as_mut_slice()[index]
+    }
+}
+
+/// The most spare heap capacity `Vekk::read_from` zeroes and offers to a reader in one call
+#[cfg(feature = "std")]
+const READ_CHUNK: usize = 8 * 1024;
+
+/// `Read::read` that rejects counts larger than `buf`, which the trait doesn't rule out
+#[cfg(feature = "std")]
+fn checked_read(r: &mut impl std::io::Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let read = r.read(buf)?;
+    if read > buf.len() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "reader returned more bytes than the buffer holds",
+        ));
+    }
+    Ok(read)
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn index_out_of_bounds(index: usize, len: usize) -> ! {
+    panic!("index {index} out of bounds for Vekk of length {len}")
+}
+
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            impl<A: Array> core::ops::Index<$range> for Vekk<A> {
+                type Output = [A::Item];
+
+                fn index(&self, index: $range) -> &[A::Item] {
+                    &self.as_slice()[index]
+                }
+            }
+
+            impl<A: Array> core::ops::IndexMut<$range> for Vekk<A> {
+                fn index_mut(&mut self, index: $range) -> &mut [A::Item] {
+                    &mut self.as_mut_slice()[index]
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(
+    core::ops::Range<usize>,
+    core::ops::RangeFrom<usize>,
+    core::ops::RangeFull,
+    core::ops::RangeInclusive<usize>,
+    core::ops::RangeTo<usize>,
+    core::ops::RangeToInclusive<usize>,
+    (Bound<usize>, Bound<usize>)
+);
+
+impl<A: Array> Default for Vekk<A> {
+    fn default() -> Self {
+        Self::from_repr(Repr::Inline {
+            len: 0,
+            array: A::default(),
+        })
+    }
+}
+
+impl<A: Array> Clone for Vekk<A>
+where
+    A: Clone,
+    A::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Self::from_repr(self.repr.clone())
+    }
+}
+
+impl<A: Array> Clone for Repr<A>
 where
     A: Clone,
     A::Item: Clone,
 {
     fn clone(&self) -> Self {
         match self {
+            // For `Copy` items the array clone is a single copy of the whole array,
+            // see the `clone_inline_256` benchmark
             Self::Inline { len, array } => Self::Inline {
                 len: *len,
                 array: array.clone(),
@@ -215,203 +1491,2581 @@ where
             Self::Heap(vec) => Self::Heap(vec.clone()),
         }
     }
-}
+}
+
+impl<A: Array, B: Array> PartialEq<Vekk<B>> for Vekk<A>
+where
+    A::Item: PartialEq<B::Item>,
+{
+    fn eq(&self, other: &Vekk<B>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<A: Array, const M: usize> PartialEq<[A::Item; M]> for Vekk<A>
+where
+    A::Item: PartialEq,
+{
+    fn eq(&self, other: &[A::Item; M]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<A: Array> Eq for Vekk<A> where A::Item: Eq {}
+
+impl<A: Array> core::fmt::Debug for Vekk<A>
+where
+    A::Item: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<A: Array> From<A> for Vekk<A> {
+    fn from(value: A) -> Self {
+        if A::CAPACITY > Self::inline_capacity() {
+            // The inline length can't represent an array this long
+            return Self::from_repr(Repr::Heap(value.into_iter().collect()));
+        }
+
+        Self::from_repr(Repr::Inline {
+            len: A::CAPACITY as u16,
+            array: value,
+        })
+    }
+}
+
+impl<A: Array> From<Vec<A::Item>> for Vekk<A>
+where
+    A::Item: Default,
+{
+    fn from(value: Vec<A::Item>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl<A: Array> From<ThinVec<A::Item>> for Vekk<A> {
+    fn from(value: ThinVec<A::Item>) -> Self {
+        Self::from_repr(Repr::Heap(value))
+    }
+}
+
+impl<A: Array> From<Vekk<A>> for Vec<A::Item>
+where
+    A::Item: Default,
+{
+    fn from(value: Vekk<A>) -> Self {
+        value.into_vec()
+    }
+}
+
+impl<A: Array> From<Vekk<A>> for ThinVec<A::Item>
+where
+    A::Item: Default,
+{
+    fn from(value: Vekk<A>) -> Self {
+        match value.repr {
+            Repr::Inline { .. } => value.into_iter().collect(),
+            Repr::Heap(vec) => vec,
+        }
+    }
+}
+
+impl<A: Array> FromIterator<A::Item> for Vekk<A>
+where
+    A::Item: Default,
+{
+    fn from_iter<T: IntoIterator<Item = A::Item>>(iter: T) -> Self {
+        let iter = iter.into_iter();
+        // Only the lower bound is trusted, an over-reported upper bound must not force an allocation
+        match iter.size_hint() {
+            (lower, _) if lower > Self::inline_capacity() => {
+                Self::from_repr(Repr::Heap(ThinVec::from_iter(iter)))
+            }
+            _ => Self::from_iter_inline_preferred(iter),
+        }
+    }
+}
+
+impl<const N: usize> Vekk<[u8; N]> {
+    pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(self.as_slice())
+    }
+
+    /// Read once from `r` into the spare capacity, growing first if there is none.
+    /// Returns the number of bytes read, like `Read::read`. On the heap, at most
+    /// 8 KiB of spare capacity are offered per call.
+    ///
+    /// A reader claiming to have read more than it was offered is an `InvalidData` error.
+    #[cfg(feature = "std")]
+    pub fn read_from(&mut self, r: &mut impl std::io::Read) -> std::io::Result<usize> {
+        if self.len() == self.capacity() {
+            self.reserve(1);
+        }
+
+        let capacity = self.capacity();
+        match &mut self.repr {
+            Repr::Inline { len, array } => {
+                let read = checked_read(r, &mut array[(*len as usize)..capacity])?;
+                *len += read as u16;
+                Ok(read)
+            }
+            Repr::Heap(vec) => {
+                // The spare heap capacity has to be initialized before handing it to the reader,
+                // so only zero a bounded chunk of it
+                let len = vec.len();
+                vec.resize(core::cmp::min(capacity, len + READ_CHUNK), 0);
+                let result = checked_read(r, &mut vec[len..]);
+                vec.truncate(len + *result.as_ref().unwrap_or(&0));
+                result
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_all_to(&self, w: &mut impl std::io::Write) -> std::io::Result<()> {
+        w.write_all(self.as_slice())
+    }
+}
+
+impl<const N: usize> From<&str> for Vekk<[u8; N]> {
+    fn from(value: &str) -> Self {
+        value.bytes().collect()
+    }
+}
+
+impl<const N: usize> core::str::FromStr for Vekk<[u8; N]> {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl<const N: usize> core::fmt::Write for Vekk<[u8; N]> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.reserve(s.len());
+        self.extend(s.bytes());
+        Ok(())
+    }
+}
+
+impl<const N: usize> core::fmt::Write for Vekk<[char; N]> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.extend(s.chars());
+        Ok(())
+    }
+}
+
+impl<const N: usize> PartialEq<str> for Vekk<[u8; N]> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_slice() == other.as_bytes()
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for Vekk<[u8; N]> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl<const N: usize> PartialEq<str> for Vekk<[char; N]> {
+    fn eq(&self, other: &str) -> bool {
+        self.iter().copied().eq(other.chars())
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for Vekk<[char; N]> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use core::mem::size_of;
+    use core::num::NonZeroUsize;
+
+    #[cfg(not(all(feature = "capacity-history", debug_assertions)))]
+    #[test]
+    fn size() {
+        assert_eq!(size_of::<Vekk<[usize; 1]>>(), 16);
+
+        // Would like this to be 8 bytes, but can't manage to trick rustc into doing that
+        assert_eq!(size_of::<Vekk<[u32; 1]>>(), 16);
+    }
+
+    #[test]
+    fn zero() {
+        let v: Vekk<[u32; 0]> = [].into_iter().collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[]);
+        assert_eq!(v.iter().collect::<Vec<_>>(), Vec::<&u32>::new());
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), Vec::<u32>::new());
+
+        let v: Vekk<[u32; 0]> = [42].into_iter().collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[42]);
+        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&42]);
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![42]);
+    }
+
+    #[test]
+    fn one() {
+        let v: Vekk<[u32; 1]> = [].into_iter().collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[]);
+        assert_eq!(v.iter().collect::<Vec<_>>(), Vec::<&u32>::new());
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![]);
+
+        let v: Vekk<[u32; 1]> = [42].into_iter().collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[42]);
+        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&42]);
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![42]);
+
+        let v: Vekk<[u32; 1]> = [1, 2].into_iter().collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2]);
+        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn push_pop() {
+        let mut v: Vekk<[u32; 1]> = Default::default();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.len(), 0);
+        assert_eq!(v.pop(), None);
+
+        v.push(1);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.len(), 1);
+        assert_eq!(v.as_slice(), &[1]);
+
+        assert_eq!(v.pop(), Some(1));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.len(), 0);
+        assert_eq!(v.as_slice(), &[]);
+
+        v.push(1);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1]);
+
+        v.push(2);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2]);
+
+        assert_eq!(v.pop(), Some(2));
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1]);
+    }
+
+    #[test]
+    fn insert1() {
+        let mut v: Vekk<[char; 4]> = Default::default();
+        v.insert(0, 'a');
+        assert_eq!(v.as_slice(), &['a']);
+    }
+
+    #[test]
+    fn insert2() {
+        let mut v: Vekk<[char; 4]> = vec!['a', 'c'].into();
+        v.insert(1, 'b');
+        assert_eq!(v.as_slice(), &['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn insert3() {
+        let mut v: Vekk<[char; 4]> = vec!['a', 'b'].into();
+        v.insert(2, 'c');
+        assert_eq!(v.as_slice(), &['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn insert4() {
+        let mut v: Vekk<[char; 4]> = vec!['a', 'b', 'd', 'e'].into();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        v.insert(2, 'c');
+        assert_eq!(v.as_slice(), &['a', 'b', 'c', 'd', 'e']);
+    }
+
+    #[test]
+    fn insert_shifts_inline() {
+        let mut v: Vekk<[char; 8]> = vec!['b', 'c', 'd'].into();
+        v.insert(0, 'a');
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn insert_extend() {
+        let mut v: Vekk<[char; 4]> = Default::default();
+
+        v.insert(0, 'b');
+        assert_eq!(v.as_slice(), &['b']);
+
+        v.extend(['d']);
+        assert_eq!(v.as_slice(), &['b', 'd']);
+
+        v.insert(1, 'c');
+        assert_eq!(v.as_slice(), &['b', 'c', 'd']);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+
+        v.insert(3, 'e');
+        assert_eq!(v.as_slice(), &['b', 'c', 'd', 'e']);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+
+        v.insert(0, 'a');
+        assert_eq!(v.as_slice(), &['a', 'b', 'c', 'd', 'e']);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+    }
+
+    #[test]
+    fn extend_from_within() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2].into();
+        v.extend_from_within(..2);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 2, 1, 2]);
+
+        v.extend_from_within(..2);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 1, 2, 1, 2]);
+
+        let mut v: Vekk<[u32; 1]> = vec![1, 2, 3].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.extend_from_within(0..=1);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extend_from_within_out_of_bounds() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2].into();
+        v.extend_from_within(1..3);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for Vekk of length 2")]
+    fn extend_from_within_inclusive_end_overflow() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2].into();
+        v.extend_from_within(1..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for Vekk of length 2")]
+    fn extend_from_within_excluded_start_overflow() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2].into();
+        v.extend_from_within((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    fn fmt_write() {
+        use core::fmt::Write;
+
+        let mut v: Vekk<[u8; 16]> = Default::default();
+        let word = "abc";
+        write!(v, "{}-{word}", 42).unwrap();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), b"42-abc");
+
+        write!(v, " and then some more").unwrap();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), b"42-abc and then some more");
+
+        let mut v: Vekk<[char; 4]> = Default::default();
+        let word = "æøå";
+        write!(v, "{word}").unwrap();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &['æ', 'ø', 'å']);
+    }
+
+    #[test]
+    fn eq_across_capacities() {
+        let a: Vekk<[u8; 4]> = vec![1, 2, 3].into();
+        let b: Vekk<[u8; 8]> = vec![1, 2, 3].into();
+        let c: Vekk<[u8; 2]> = vec![1, 2, 3].into();
+        assert!(matches!(c.repr, Repr::Heap(_)));
+
+        assert!(a == b);
+        assert!(b == a);
+        assert!(a == c);
+        assert!(a == a.clone());
+
+        let d: Vekk<[u8; 8]> = vec![1, 2].into();
+        assert!(a != d);
+    }
+
+    #[test]
+    fn drain_front_inline() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3, 4].into();
+        v.drain_front(1);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[2, 3, 4]);
+
+        v.drain_front(0);
+        assert_eq!(v.as_slice(), &[2, 3, 4]);
+
+        v.push(5);
+        assert_eq!(v.as_slice(), &[2, 3, 4, 5]);
+
+        v.drain_front(10);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn drain_front_heap() {
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3, 4, 5].into();
+        v.drain_front(1);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[2, 3, 4, 5]);
+
+        v.drain_front(2);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[4, 5]);
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        v.drain_front(3);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn as_chunks() {
+        let v: Vekk<[u8; 16]> = (0..10).collect();
+        let (chunks, remainder) = v.as_chunks::<4>();
+        assert_eq!(chunks, &[[0, 1, 2, 3], [4, 5, 6, 7]]);
+        assert_eq!(remainder, &[8, 9]);
+
+        let (remainder, chunks) = v.as_rchunks::<4>();
+        assert_eq!(remainder, &[0, 1]);
+        assert_eq!(chunks, &[[2, 3, 4, 5], [6, 7, 8, 9]]);
+    }
+
+    #[test]
+    fn clear_if() {
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        v.clear_if(|items| items.len() > 3);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+        v.clear_if(|items| items.contains(&2));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn remove() {
+        let mut v: Vekk<[char; 4]> = vec!['a', 'b', 'c'].into();
+        assert_eq!(v.remove(1), 'b');
+        assert_eq!(v.as_slice(), &['a', 'c']);
+        assert_eq!(v.remove(1), 'c');
+        assert_eq!(v.remove(0), 'a');
+        assert!(v.is_empty());
+
+        let mut v: Vekk<[char; 1]> = vec!['a', 'b', 'c'].into();
+        assert_eq!(v.remove(0), 'a');
+        assert_eq!(v.as_slice(), &['b', 'c']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_out_of_bounds() {
+        let mut v: Vekk<[char; 4]> = vec!['a'].into();
+        v.remove(1);
+    }
+
+    #[test]
+    fn deque() {
+        use crate::deque::VekkDeque;
+
+        let mut d: VekkDeque<[u32; 3]> = Default::default();
+        assert_eq!(d.pop_front(), None);
+        assert_eq!(d.pop_back(), None);
+
+        d.push_back(2);
+        d.push_front(1);
+        d.push_back(3);
+        assert_eq!(d.as_slice(), &[1, 2, 3]);
+        assert_eq!(d.front(), Some(&1));
+        assert_eq!(d.back(), Some(&3));
+
+        d.push_front(0);
+        assert_eq!(d.as_slice(), &[0, 1, 2, 3]);
+        assert_eq!(d.len(), 4);
+
+        assert_eq!(d.pop_front(), Some(0));
+        assert_eq!(d.pop_back(), Some(3));
+        assert_eq!(d.pop_front(), Some(1));
+        assert_eq!(d.as_slice(), &[2]);
+
+        let v = d.into_inner();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[2]);
+    }
+
+    #[test]
+    fn into_iter_nth_count_last() {
+        let v: Vekk<[u32; 8]> = (1..=5).collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        let mut iter = v.into_iter();
+        assert_eq!(iter.nth(2), Some(3));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+
+        let v: Vekk<[u32; 2]> = (1..=5).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        let mut iter = v.into_iter();
+        assert_eq!(iter.nth(2), Some(3));
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+        assert_eq!(iter.nth(5), None);
+        assert_eq!(iter.next(), None);
+
+        let v: Vekk<[u32; 8]> = (1..=5).collect();
+        assert_eq!(v.clone().into_iter().count(), 5);
+        assert_eq!(v.clone().into_iter().last(), Some(5));
+        let mut iter = v.into_iter();
+        iter.nth(4);
+        assert_eq!(iter.last(), None);
+
+        let v: Vekk<[u32; 2]> = (1..=5).collect();
+        assert_eq!(v.clone().into_iter().count(), 5);
+        assert_eq!(v.into_iter().last(), Some(5));
+    }
+
+    #[test]
+    fn into_owned_slice() {
+        let v: Vekk<[u32; 4]> = vec![1, 2].into();
+        assert_eq!(&*v.into_owned_slice(), &[1, 2]);
+
+        let v: Vekk<[u32; 1]> = vec![1, 2].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(&*v.into_owned_slice(), &[1, 2]);
+
+        let v: Vekk<[u32; 4]> = Default::default();
+        assert!(v.into_owned_slice().is_empty());
+    }
+
+    #[test]
+    fn rsplit() {
+        let v: Vekk<[u8; 16]> = b"usr/local/bin".iter().copied().collect();
+        assert_eq!(
+            v.rsplit(|b| *b == b'/').collect::<Vec<_>>(),
+            vec![&b"bin"[..], b"local", b"usr"]
+        );
+        assert_eq!(
+            v.rsplitn(2, |b| *b == b'/').collect::<Vec<_>>(),
+            vec![&b"bin"[..], b"usr/local"]
+        );
+    }
+
+    #[test]
+    fn rchunks() {
+        let mut v: Vekk<[u8; 2]> = (1..=5).collect();
+        assert_eq!(
+            v.rchunks(2).collect::<Vec<_>>(),
+            vec![&[4, 5][..], &[2, 3], &[1]]
+        );
+
+        for chunk in v.rchunks_mut(2) {
+            chunk.reverse();
+        }
+        assert_eq!(v.as_slice(), &[1, 3, 2, 5, 4]);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        v.truncate(5);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        v.truncate(1);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1]);
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3, 4].into();
+        let capacity = v.capacity();
+        v.truncate(3);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        v.truncate(1);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), capacity);
+        assert_eq!(v.as_slice(), &[1]);
+        v.truncate(0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), capacity);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn truncate_keep_alloc() {
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3, 4].into();
+        v.truncate_keep_alloc(1);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1]);
+
+        v.truncate_keep_alloc(0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert!(v.is_empty());
+
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        v.truncate_keep_alloc(2);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn truncate_and_shrink() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        v.truncate_and_shrink(1);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.capacity(), 4);
+        assert_eq!(v.as_slice(), &[1]);
+
+        let mut v: Vekk<[u32; 2]> = Vekk::with_capacity(16);
+        v.extend([1, 2, 3, 4]);
+        v.truncate_and_shrink(3);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), 3);
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+        v.truncate_and_shrink(2);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.capacity(), 2);
+        assert_eq!(v.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn with_capacity() {
+        let v: Vekk<[u32; 4]> = Vekk::with_capacity(4);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+
+        let v: Vekk<[u32; 4]> = Vekk::with_capacity(5);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn zero_sized_items() {
+        let mut v: Vekk<[(); 4]> = Default::default();
+        for _ in 0..1000 {
+            v.push(());
+        }
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), 1000);
+
+        v.insert(500, ());
+        assert_eq!(v.pop(), Some(()));
+        assert_eq!(v.len(), 1000);
+
+        v.truncate_and_shrink(3);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[(), (), ()]);
+
+        let v: Vekk<[(); 4]> = core::iter::repeat_n((), 10_000).collect();
+        assert_eq!(v.len(), 10_000);
+        assert_eq!(v.into_iter().count(), 10_000);
+
+        let mut v: Vekk<[(); 4]> = Vekk::with_capacity(10_000);
+        v.extend(core::iter::repeat_n((), 10_000));
+        assert_eq!(v.len(), 10_000);
+    }
+
+    #[test]
+    fn pop_front() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        assert_eq!(v.pop_front(), Some(1));
+        assert_eq!(v.pop_front(), Some(2));
+        assert_eq!(v.pop_front(), Some(3));
+        assert_eq!(v.pop_front(), None);
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3, 4].into();
+        assert_eq!(v.pop_front(), Some(1));
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.pop_front(), Some(2));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.pop_front(), Some(3));
+        assert_eq!(v.pop_front(), Some(4));
+        assert_eq!(v.pop_front(), None);
+    }
+
+    #[test]
+    fn collect_result_and_option() {
+        let v: Result<Vekk<[u32; 2]>, &str> = [Ok(1), Ok(2), Ok(3)].into_iter().collect();
+        assert_eq!(v.unwrap().as_slice(), &[1, 2, 3]);
+
+        let mut consumed = 0;
+        let v: Result<Vekk<[u32; 2]>, &str> = [Ok(1), Err("two"), Ok(3)]
+            .into_iter()
+            .inspect(|_| consumed += 1)
+            .collect();
+        assert!(matches!(v, Err("two")));
+        assert_eq!(consumed, 2);
+
+        let v: Option<Vekk<[u32; 2]>> = [Some(1), Some(2)].into_iter().collect();
+        assert_eq!(v.unwrap().as_slice(), &[1, 2]);
+
+        let v: Option<Vekk<[u32; 2]>> = [Some(1), None, Some(3)].into_iter().collect();
+        assert!(v.is_none());
+    }
+
+    #[test]
+    fn chunk_by() {
+        let v: Vekk<[u32; 8]> = vec![1, 1, 2, 3, 3].into();
+        assert_eq!(
+            v.chunk_by(|a, b| a == b).collect::<Vec<_>>(),
+            vec![&[1, 1][..], &[2], &[3, 3]]
+        );
+
+        let v: Vekk<[u32; 8]> = Default::default();
+        assert_eq!(v.chunk_by(|a, b| a == b).count(), 0);
+    }
+
+    #[test]
+    fn resize() {
+        let mut v: Vekk<[u32; 4]> = vec![1].into();
+        v.resize(3, 7);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 7, 7]);
+
+        v.resize(5, 8);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 7, 7, 8, 8]);
+
+        v.resize(1, 9);
+        assert_eq!(v.as_slice(), &[1]);
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut v: Vekk<[u32; 4]> = vec![1].into();
+        v.extend_from_slice(&[2, 3]);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        v.extend_from_slice(&[4, 5]);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5]);
+    }
+
+    std::thread_local! {
+        static CLONES: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+        static LIVE: core::cell::Cell<isize> = const { core::cell::Cell::new(0) };
+    }
+
+    /// Panics on the third clone after resetting CLONES, and tracks live instances to detect leaks
+    struct Fragile(u32);
+
+    impl Fragile {
+        fn new(n: u32) -> Self {
+            LIVE.set(LIVE.get() + 1);
+            Self(n)
+        }
+    }
+
+    impl Default for Fragile {
+        fn default() -> Self {
+            Self::new(0)
+        }
+    }
+
+    impl Clone for Fragile {
+        fn clone(&self) -> Self {
+            CLONES.set(CLONES.get() + 1);
+            if CLONES.get() == 3 {
+                panic!("third clone");
+            }
+            Self::new(self.0)
+        }
+    }
+
+    impl Drop for Fragile {
+        fn drop(&mut self) {
+            LIVE.set(LIVE.get() - 1);
+        }
+    }
+
+    #[test]
+    fn clone_panic_safety() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        {
+            let mut v: Vekk<[Fragile; 4]> = vec![Fragile::new(1)].into();
+            let source = [Fragile::new(2), Fragile::new(3), Fragile::new(4)];
+
+            CLONES.set(0);
+            assert!(catch_unwind(AssertUnwindSafe(|| v.extend_from_slice(&source))).is_err());
+            assert_eq!(v.len(), 3);
+            assert_eq!(v.iter().map(|f| f.0).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+            CLONES.set(0);
+            assert!(catch_unwind(AssertUnwindSafe(|| v.resize(10, Fragile::new(9)))).is_err());
+            assert!(matches!(v.repr, Repr::Heap(_)));
+            assert_eq!(v.len(), 5);
+            assert_eq!(
+                v.iter().map(|f| f.0).collect::<Vec<_>>(),
+                vec![1, 2, 3, 9, 9]
+            );
+
+            CLONES.set(0);
+            assert!(catch_unwind(AssertUnwindSafe(|| v.extend_from_within(..))).is_err());
+            assert_eq!(v.len(), 7);
+
+            v.push(Fragile::new(10));
+            assert_eq!(v.len(), 8);
+        }
+
+        assert_eq!(LIVE.get(), 0);
+    }
+
+    #[test]
+    fn iter_mut_both_ends() {
+        let mut v: Vekk<[u32; 8]> = (1..=5).collect();
+        let mut iter = v.iter_mut();
+        assert_eq!(iter.len(), 5);
+        *iter.next().unwrap() *= 10;
+        *iter.next_back().unwrap() *= 100;
+        assert_eq!(iter.len(), 3);
+        for item in iter.rev() {
+            *item += 1;
+        }
+        assert_eq!(v.as_slice(), &[10, 3, 4, 5, 500]);
+
+        let mut v: Vekk<[u32; 2]> = (1..=5).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        let mut iter = v.iter_mut();
+        *iter.next_back().unwrap() = 0;
+        *iter.next().unwrap() = 0;
+        assert_eq!(iter.len(), 3);
+        for item in &mut v {
+            *item += 1;
+        }
+        assert_eq!(v.as_slice(), &[1, 3, 4, 5, 1]);
+
+        let mut iter = v.iter_mut();
+        while iter.next().is_some() {}
+        assert!(iter.next().is_none());
+        assert!(iter.next_back().is_none());
+    }
+
+    #[test]
+    fn try_into_inline_array() {
+        let v: Vekk<[u32; 3]> = vec![1, 2, 3].into();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.try_into_inline_array().ok(), Some([1, 2, 3]));
+
+        let mut v: Vekk<[u32; 3]> = vec![1, 2, 3, 4].into();
+        v.pop();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.try_into_inline_array().ok(), Some([1, 2, 3]));
+
+        let v: Vekk<[u32; 3]> = vec![1, 2].into();
+        assert_eq!(v.try_into_inline_array().unwrap_err().as_slice(), &[1, 2]);
+
+        let v: Vekk<[u32; 3]> = vec![1, 2, 3, 4].into();
+        assert_eq!(
+            v.try_into_inline_array().unwrap_err().as_slice(),
+            &[1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn inline_array() {
+        let mut v: Vekk<[u32; 2]> = vec![1].into();
+        assert_eq!(&v.inline_array().unwrap()[..1], &[1]);
+
+        v.push(2);
+        assert_eq!(v.inline_array(), Some(&[1, 2]));
+
+        v.push(3);
+        assert_eq!(v.inline_array(), None);
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let mut v: Vekk<[(u32, char); 4]> = vec![(1, 'a'), (1, 'b'), (2, 'c')].into();
+        v.dedup_by_key(|item| item.0);
+        assert_eq!(v.as_slice(), &[(1, 'a'), (2, 'c')]);
+
+        let mut v: Vekk<[(u32, char); 2]> =
+            vec![(1, 'a'), (1, 'b'), (2, 'c'), (2, 'd'), (1, 'e')].into();
+        v.dedup_by_key(|item| item.0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[(1, 'a'), (2, 'c'), (1, 'e')]);
+
+        let mut v: Vekk<[(u32, char); 2]> = vec![(1, 'a'), (1, 'b'), (2, 'c')].into();
+        v.dedup_by_key(|item| item.0);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[(1, 'a'), (2, 'c')]);
+    }
+
+    #[test]
+    fn dedup_by() {
+        let mut v: Vekk<[&str; 8]> = vec!["a", "A", "b", "c", "C", "c", "a"].into();
+        v.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(v.as_slice(), &["a", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn min_max() {
+        let v: Vekk<[u32; 2]> = Default::default();
+        assert_eq!(v.min(), None);
+        assert_eq!(v.max(), None);
+
+        let v: Vekk<[u32; 2]> = vec![3, 1, 4].into();
+        assert_eq!(v.min(), Some(&1));
+        assert_eq!(v.max(), Some(&4));
+    }
+
+    #[test]
+    fn into_vec() {
+        let v: Vekk<[u32; 4]> = vec![1, 2].into();
+        let vec: Vec<u32> = v.into();
+        assert_eq!(vec, [1, 2]);
+
+        let v: Vekk<[u32; 1]> = vec![1, 2].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        let vec: Vec<u32> = v.into();
+        assert_eq!(vec, [1, 2]);
+    }
+
+    #[test]
+    fn into_thin_vec() {
+        let v: Vekk<[u32; 4]> = vec![1, 2].into();
+        assert_eq!(v.capacity(), 4);
+        let vec: ThinVec<u32> = v.into();
+        assert_eq!(vec.as_slice(), &[1, 2]);
+
+        let mut v: Vekk<[u32; 4]> = Vekk::with_capacity(100);
+        v.extend([1, 2, 3]);
+        let capacity = v.capacity();
+        let ptr = v.as_ptr();
+        assert!(capacity >= 100);
+
+        let vec: ThinVec<u32> = v.into();
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+        assert_eq!(vec.capacity(), capacity);
+        assert_eq!(vec.as_ptr(), ptr);
+    }
+
+    #[cfg(feature = "compat")]
+    #[test]
+    fn compat() {
+        use crate::compat::{SmallVec, SmallVecExt};
+
+        let mut v: SmallVec<[u32; 2]> = SmallVec::new();
+        assert_eq!(v.inline_size(), 2);
+        assert!(!v.spilled());
+
+        v.extend([1, 2, 3]);
+        assert!(v.spilled());
+
+        let v: SmallVec<[u32; 2]> = SmallVec::from_vec(vec![1]);
+        assert!(!v.spilled());
+        assert_eq!(v.as_slice(), &[1]);
+
+        let v = SmallVec::from_buf([1, 2]);
+        assert_eq!(v.len(), 2);
+        assert_eq!(v.into_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn new() {
+        let v: Vekk<[u32; 2]> = Vekk::new();
+        assert!(v.is_empty());
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+    }
+
+    #[test]
+    fn into_iter_fold() {
+        let v: Vekk<[u32; 8]> = (1..=5).collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.iter().sum::<u32>(), 15);
+        assert_eq!(v.into_iter().fold(0, |a, b| a + u64::from(b)), 15);
+
+        let v: Vekk<[u32; 2]> = (1..=5).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.into_iter().fold(0, |a, b| a + u64::from(b)), 15);
+
+        let v: Vekk<[u32; 8]> = (1..=5).collect();
+        let mut iter = v.into_iter();
+        iter.next();
+        assert_eq!(
+            iter.fold(Vec::new(), |mut acc, b| {
+                acc.push(b);
+                acc
+            }),
+            vec![2, 3, 4, 5]
+        );
+    }
+
+    #[test]
+    fn retain() {
+        let mut v: Vekk<[u32; 8]> = (1..=6).collect();
+        v.retain(|n| n % 3 != 0);
+        assert_eq!(v.as_slice(), &[1, 2, 4, 5]);
+
+        let mut v: Vekk<[u32; 2]> = (1..=6).collect();
+        v.retain(|n| n % 3 != 0);
+        assert_eq!(v.as_slice(), &[1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut v: Vekk<[char; 8]> = "abcde".chars().collect();
+        v.retain_indexed(|index, _| index % 2 == 0);
+        assert_eq!(v.as_slice(), &['a', 'c', 'e']);
+
+        let mut v: Vekk<[char; 2]> = "abcde".chars().collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.retain_indexed(|index, _| index % 2 == 0);
+        assert_eq!(v.as_slice(), &['a', 'c', 'e']);
+    }
+
+    #[test]
+    fn push_rolling() {
+        let mut v: Vekk<[u32; 3]> = Default::default();
+        for n in 1..=5 {
+            v.push_rolling(3, n);
+            assert!(matches!(v.repr, Repr::Inline { .. }));
+        }
+        assert_eq!(v.as_slice(), &[3, 4, 5]);
+
+        v.push_rolling(1, 6);
+        assert_eq!(v.as_slice(), &[6]);
+
+        v.push_rolling(0, 7);
+        assert!(v.is_empty());
+    }
+
+    #[test]
+    fn extend_through_spill() {
+        let mut v: Vekk<[u32; 4]> = vec![0].into();
+        v.extend((1..100).filter(|n| n % 7 != 0));
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(
+            v.as_slice(),
+            (0..100)
+                .filter(|n| n % 7 != 0 || *n == 0)
+                .collect::<Vec<_>>()
+        );
+
+        v.extend([1000]);
+        assert_eq!(v.last(), Some(&1000));
+    }
+
+    #[test]
+    fn push_unique() {
+        let mut v: Vekk<[u32; 2]> = Default::default();
+        assert!(v.push_unique(1));
+        assert!(!v.push_unique(1));
+        assert!(v.push_unique(2));
+        assert!(v.push_unique(3));
+        assert!(!v.push_unique(2));
+        assert_eq!(v.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn into_iter_as_slice() {
+        let v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        let mut iter = v.into_iter();
+        assert_eq!(iter.as_slice(), &[1, 2, 3]);
+        iter.next();
+        assert_eq!(iter.as_slice(), &[2, 3]);
+        iter.nth(1);
+        assert_eq!(iter.as_slice(), &[]);
+
+        let v: Vekk<[u32; 1]> = vec![1, 2, 3].into();
+        let mut iter = v.into_iter();
+        assert_eq!(iter.as_slice(), &[1, 2, 3]);
+        iter.next();
+        assert_eq!(iter.as_slice(), &[2, 3]);
+    }
+
+    #[test]
+    fn compact() {
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        v.compact();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        v.pop();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.compact();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 2]);
+
+        v.compact();
+        assert_eq!(v.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn eq_arrays() {
+        let v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        assert_eq!(v, [1, 2, 3]);
+        assert_ne!(v, [1, 2]);
+        assert_ne!(v, [1, 2, 3, 4, 5]);
+
+        let v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(alloc::format!("{v:?}"), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn get_disjoint_mut() {
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        let [a, b] = v.get_disjoint_mut([2, 0]).unwrap();
+        core::mem::swap(a, b);
+        assert_eq!(v, [3, 2, 1]);
+
+        assert!(v.get_disjoint_mut([1, 1]).is_none());
+        assert!(v.get_disjoint_mut([0, 3]).is_none());
+        assert!(v.get_disjoint_mut([]).is_some());
+    }
+
+    #[test]
+    fn insert_sorted() {
+        let mut v: Vekk<[u32; 4]> = Default::default();
+        assert_eq!(v.insert_sorted(5), 0);
+        assert_eq!(v.insert_sorted(1), 0);
+        assert_eq!(v.insert_sorted(3), 1);
+        assert_eq!(v.insert_sorted(9), 3);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.insert_sorted(4), 2);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn from_iter_spill_keeps_every_item() {
+        // `take_while` has no upper bound, so this goes through the inline path first
+        let v: Vekk<[u32; 2]> = (1..).take_while(|n| *n <= 5).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn u16_max_inline_capacity() {
+        const MAX: usize = u16::MAX as usize;
+
+        let mut v: Vekk<[u8; MAX]> = Default::default();
+        v.extend(core::iter::repeat_n(1, MAX));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.len(), MAX);
+
+        v.push(2);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), MAX + 1);
+
+        v.truncate_and_shrink(MAX);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        v.insert(0, 3);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), MAX + 1);
+
+        let mut iter = Vekk::<[u8; MAX]>::from([4; MAX]).into_iter();
+        assert_eq!(iter.nth(MAX - 1), Some(4));
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn beyond_u16_max_capacity() {
+        const LEN: usize = u16::MAX as usize + 1;
+
+        let v: Vekk<[u8; LEN]> = [7; LEN].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), LEN);
+
+        let v: Vekk<[u8; LEN]> = (0..).take_while(|n| *n < LEN).map(|n| n as u8).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), LEN);
+        assert_eq!(v[LEN - 1], (LEN - 1) as u8);
+
+        let v: Vekk<[u8; LEN]> = core::iter::repeat_n(1, LEN).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), v.len());
+    }
+
+    #[test]
+    fn capacity_requests_beyond_u16_max() {
+        const MAX: usize = u16::MAX as usize;
+        type Huge = [u8; MAX + 100];
+
+        let v = Vekk::<Huge>::with_capacity(MAX);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.capacity(), MAX);
+
+        let v = Vekk::<Huge>::with_capacity(MAX + 1);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert!(v.capacity() > MAX);
+
+        let mut v = Vekk::<Huge>::default();
+        v.reserve(MAX);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        v.reserve_exact(MAX + 1);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), MAX + 1);
+    }
+
+    #[test]
+    fn str_round_trip() {
+        let v: Vekk<[u8; 8]> = "hello".parse().unwrap();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_str(), Ok("hello"));
+
+        let v = Vekk::<[u8; 8]>::from("hello, world");
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_str(), Ok("hello, world"));
+
+        let v: Vekk<[u8; 8]> = vec![b'a', 0xff].into();
+        assert!(v.as_str().is_err());
+    }
+
+    #[test]
+    fn shrink_without_drop_glue() {
+        let mut v: Vekk<[u8; 256]> = core::iter::repeat_n(7, 256).collect();
+
+        // Removed bytes are left behind instead of being overwritten with defaults
+        v.truncate(10);
+        assert_eq!(v.len(), 10);
+        assert_eq!(v.inline_array().unwrap()[10], 7);
+
+        v.drain_front(5);
+        assert_eq!(v, [7; 5]);
+        v.retain(|_| false);
+        assert!(v.is_empty());
+        assert_eq!(v.inline_array().unwrap()[..], [7; 256]);
+
+        v.extend(0..=255);
+        v.clear();
+        assert!(v.is_empty());
+        assert_eq!(v.inline_array().unwrap()[255], 255);
+
+        v.push(1);
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn extend_vekks() {
+        let mut v: Vekk<[u32; 4]> = Default::default();
+        v.extend_vekks([
+            Vekk::from(vec![1, 2]),
+            Vekk::default(),
+            Vekk::from(vec![3]),
+            Vekk::from(vec![4, 5]),
+        ]);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 2, 3, 4, 5]);
+
+        let mut v: Vekk<[u32; 4]> = vec![0].into();
+        v.extend_vekks([Vekk::from(vec![1, 2, 3, 4, 5])]);
+        assert_eq!(v, [0, 1, 2, 3, 4, 5]);
+
+        // Growing per source would have gone through capacities 4, 8 and 16
+        let mut v: Vekk<[u32; 2]> = vec![0].into();
+        v.extend_vekks([
+            Vekk::from(vec![1, 2]),
+            Vekk::from(vec![3, 4]),
+            Vekk::from(vec![5, 6, 7, 8, 9]),
+        ]);
+        assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(v.capacity(), 10);
+    }
+
+    #[cfg(feature = "unstable")]
+    #[test]
+    fn trusted_len_collect() {
+        fn assert_trusted_len<I: core::iter::TrustedLen>(iter: I) -> I {
+            iter
+        }
+
+        let v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        let collected: Vec<_> = assert_trusted_len(v.into_iter()).collect();
+        assert_eq!(collected, [1, 2, 3]);
+
+        let v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        let collected: Vec<_> = assert_trusted_len(v.into_iter()).collect();
+        assert_eq!(collected, [1, 2, 3]);
+    }
+
+    #[test]
+    fn remaining_inline_capacity() {
+        let mut v: Vekk<[u32; 3]> = Default::default();
+        assert_eq!(v.remaining_inline_capacity(), 3);
+        v.push(1);
+        assert_eq!(v.remaining_inline_capacity(), 2);
+        v.extend([2, 3]);
+        assert_eq!(v.remaining_inline_capacity(), 0);
+        v.push(4);
+        assert_eq!(v.remaining_inline_capacity(), 0);
+        v.truncate_and_shrink(1);
+        assert_eq!(v.remaining_inline_capacity(), 2);
+    }
+
+    #[test]
+    fn from_thin_vec() {
+        let vec: ThinVec<u32> = (0..10).collect();
+        let ptr = vec.as_ptr();
+        let capacity = vec.capacity();
+
+        let v: Vekk<[u32; 4]> = vec.into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_ptr(), ptr);
+        assert_eq!(v.capacity(), capacity);
+        assert_eq!(v.len(), 10);
+
+        let mut v: Vekk<[u32; 4]> = ThinVec::from([1, 2]).into();
+        v.compact();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [1, 2]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde_test::{assert_tokens, Token};
+
+        let v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        assert_tokens(
+            &v,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::U32(1),
+                Token::U32(2),
+                Token::U32(3),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deserialize_bounded() {
+        use ::serde::de::value::{Error, SeqDeserializer};
+
+        let de = SeqDeserializer::<_, Error>::new([1u32, 2, 3].into_iter());
+        let v = Vekk::<[u32; 2]>::deserialize_bounded(de, 3).unwrap();
+        assert_eq!(v, [1, 2, 3]);
+
+        let de = SeqDeserializer::<_, Error>::new([1u32, 2, 3, 4].into_iter());
+        let error = Vekk::<[u32; 2]>::deserialize_bounded(de, 3).unwrap_err();
+        assert_eq!(
+            alloc::format!("{error}"),
+            "invalid length 4, expected a sequence of at most 3 elements"
+        );
+    }
+
+    #[test]
+    fn index_scalar_and_range() {
+        fn first<A: Array>(v: &Vekk<A>) -> &A::Item {
+            &v[0]
+        }
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        let x: &u32 = &v[0];
+        assert_eq!(*x, 1);
+        assert_eq!(*first(&v), 1);
+
+        let s: &[u32] = &v[1..];
+        assert_eq!(s, &[2, 3]);
+        assert_eq!(&v[..=1], &[1, 2]);
+        assert_eq!(&v[..], &[1, 2, 3]);
+
+        v[2] = 5;
+        v[..2].copy_from_slice(&[3, 4]);
+        assert_eq!(v, [3, 4, 5]);
+    }
+
+    #[cfg(feature = "instrument")]
+    #[test]
+    fn spill_hook_fires_once_per_transition() {
+        use crate::instrument::{self, SpillEvent, Transition};
+
+        std::thread_local! {
+            static EVENTS: core::cell::RefCell<Vec<(Transition, usize)>> = const { core::cell::RefCell::new(Vec::new()) };
+        }
+
+        // The hook is global, other tests may trigger it from their own threads
+        fn hook(event: &SpillEvent) {
+            if event.type_name == core::any::type_name::<[u32; 2]>() {
+                assert_eq!(event.inline_capacity, 2);
+                EVENTS.with(|events| events.borrow_mut().push((event.transition, event.len)));
+            }
+        }
+
+        instrument::set_spill_hook(Some(hook));
+
+        let mut v: Vekk<[u32; 2]> = Vekk::default();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+        v.push(4);
+        v.truncate_and_shrink(3);
+        v.truncate_and_shrink(2);
+        v.truncate_and_shrink(1);
+        v.insert(0, 0);
+        v.insert(0, 0);
+        v.clear();
+        v.clear();
+
+        assert_eq!(
+            EVENTS.with(|events| events.take()),
+            vec![
+                (Transition::Spill, 3),
+                (Transition::Compact, 2),
+                (Transition::Spill, 3),
+                (Transition::Compact, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn reserve_one_amortizes_growth() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        let mut capacity_changes = 0;
+        let mut capacity = v.capacity();
+        for i in 0..10_000 {
+            v.reserve(1);
+            v.push(i);
+            if v.capacity() != capacity {
+                capacity = v.capacity();
+                capacity_changes += 1;
+            }
+        }
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.len(), 10_000);
+        assert!(capacity_changes <= 16, "{capacity_changes} reallocations");
+    }
+
+    #[test]
+    fn spill_doubles_inline_capacity() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3, 4].into();
+        v.reserve(1);
+        assert_eq!(v.capacity(), 8);
+
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3, 4].into();
+        v.push(5);
+        assert_eq!(v.capacity(), 8);
+
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3, 4].into();
+        v.reserve(100);
+        assert_eq!(v.capacity(), 104);
+    }
+
+    /// Item type for testing paths that must not require `Default`
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct NoDefault(u64);
+
+    struct NoDefaultArray([NoDefault; 4]);
+
+    impl IntoIterator for NoDefaultArray {
+        type Item = NoDefault;
+        type IntoIter = core::array::IntoIter<NoDefault, 4>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.into_iter()
+        }
+    }
+
+    // SAFETY: Both slices are the wrapped array
+    unsafe impl Array for NoDefaultArray {
+        const CAPACITY: usize = 4;
+
+        fn default() -> Self {
+            Self([NoDefault(0); 4])
+        }
+
+        fn as_slice(&self) -> &[NoDefault] {
+            &self.0
+        }
+
+        fn as_slice_mut(&mut self) -> &mut [NoDefault] {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn into_iter_copy_without_default() {
+        let v = Vekk::from(NoDefaultArray([1, 2, 3, 4].map(NoDefault)));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(
+            v.into_iter().collect::<Vec<_>>(),
+            [1, 2, 3, 4].map(NoDefault).to_vec()
+        );
+
+        let mut iter = Vekk::from(NoDefaultArray([1, 2, 3, 4].map(NoDefault))).into_iter();
+        assert_eq!(iter.nth(1), Some(NoDefault(2)));
+        assert_eq!(iter.last(), Some(NoDefault(4)));
+    }
+
+    #[test]
+    fn into_iter_drops_each_item_once() {
+        LIVE.set(0);
+        let make = || -> Vekk<[Fragile; 8]> { (0..5).map(Fragile::new).collect() };
+
+        drop(make().into_iter());
+        assert_eq!(LIVE.get(), 0);
+
+        let mut iter = make().into_iter();
+        assert_eq!(iter.next().map(|f| f.0), Some(0));
+        assert_eq!(iter.nth(1).map(|f| f.0), Some(2));
+        assert_eq!(LIVE.get(), 2 + 3);
+        drop(iter);
+        assert_eq!(LIVE.get(), 0);
+
+        assert_eq!(make().into_iter().last().map(|f| f.0), Some(4));
+        assert_eq!(make().into_iter().nth(10).map(|f| f.0), None);
+        assert_eq!(make().into_iter().fold(0, |acc, f| acc + f.0), 10);
+        assert_eq!(LIVE.get(), 0);
+    }
+
+    #[test]
+    fn out_of_bounds_panic_messages() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+        use std::string::String;
+
+        fn panic_message(f: impl FnOnce()) -> String {
+            let payload = catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+            *payload.downcast::<String>().unwrap()
+        }
+
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3].into();
+        assert_eq!(
+            panic_message(|| {
+                let _ = v[5];
+            }),
+            "index 5 out of bounds for Vekk of length 3"
+        );
+        assert_eq!(
+            panic_message(|| v[3] = 0),
+            "index 3 out of bounds for Vekk of length 3"
+        );
+        assert_eq!(
+            panic_message(|| v.insert(4, 0)),
+            "index 4 out of bounds for Vekk of length 3"
+        );
+        assert_eq!(
+            panic_message(|| {
+                v.remove(3);
+            }),
+            "index 3 out of bounds for Vekk of length 3"
+        );
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        assert_eq!(
+            panic_message(|| v.insert(5, 0)),
+            "index 5 out of bounds for Vekk of length 3"
+        );
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_all_to_writer() {
+        let v: Vekk<[u8; 4]> = "hello".into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        let mut out: Vec<u8> = vec![];
+        v.write_all_to(&mut out).unwrap();
+        v.write_all_to(&mut out).unwrap();
+        assert_eq!(out, b"hellohello");
+    }
+
+    #[test]
+    fn from_iter_inline_preferred_ignores_size_hint() {
+        let iter = || (0..1000u32).filter(|n| *n == 500);
+        assert_eq!(iter().size_hint(), (0, Some(1000)));
+
+        let v = Vekk::<[u32; 4]>::from_iter_inline_preferred(iter());
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [500]);
+
+        let v = Vekk::<[u32; 4]>::from_iter_inline_preferred(0..6);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn dedup_and_compact_spilled() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 1, 2, 2, 2, 3, 3, 1].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        v.dedup_and_compact();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [1, 2, 3, 1]);
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 1, 2, 3].into();
+        v.dedup_and_compact();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn split_off_into_smaller_array() {
+        let mut v: Vekk<[u8; 8]> = (1..=6).collect();
+        let tail: Vekk<[u8; 2]> = v.split_off_into(4);
+        assert!(matches!(tail.repr, Repr::Inline { .. }));
+        assert_eq!(tail, [5, 6]);
+        assert_eq!(v, [1, 2, 3, 4]);
+
+        let tail: Vekk<[u8; 2]> = v.split_off_into(1);
+        assert!(matches!(tail.repr, Repr::Heap(_)));
+        assert_eq!(tail, [2, 3, 4]);
+        assert_eq!(v, [1]);
+
+        let tail: Vekk<[u8; 2]> = v.split_off_into(1);
+        assert!(tail.is_empty());
+        assert_eq!(v, [1]);
+    }
+
+    #[test]
+    fn filter_map_in_place_doubles_evens() {
+        let double_even = |n: u32| n.is_multiple_of(2).then_some(n * 2);
+
+        let mut v: Vekk<[u32; 8]> = (1..=6).collect();
+        v.filter_map_in_place(double_even);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [4, 8, 12]);
+
+        let mut v: Vekk<[u32; 2]> = (1..=6).collect();
+        v.filter_map_in_place(double_even);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [4, 8, 12]);
+    }
+
+    #[test]
+    fn clone_into_vec_exact_capacity() {
+        let v: Vekk<[u32; 8]> = (1..=3).collect();
+        let vec = v.clone_into_vec();
+        assert_eq!(vec, [1, 2, 3]);
+        assert_eq!(vec.capacity(), vec.len());
+
+        let mut v: Vekk<[u32; 2]> = Vekk::with_capacity(100);
+        v.extend(1..=3);
+        let vec = v.clone_into_vec();
+        assert_eq!(vec, [1, 2, 3]);
+        assert_eq!(vec.capacity(), vec.len());
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    #[test]
+    fn slice_of_cells() {
+        let mut v: Vekk<[u32; 4]> = vec![0, 1, 2, 3].into();
+        let cells = v.as_slice_of_cells();
+        for cell in cells {
+            cells[0].set(cells[0].get() + cell.get());
+        }
+        cells[3].swap(&cells[1]);
+        assert_eq!(v, [6, 3, 2, 1]);
+    }
+
+    #[test]
+    fn retain_matches_vec() {
+        for mask in [0u32, 0b1, 0b1010_1010, 0b1111_0000, 0b0111_1111, u32::MAX] {
+            let keep = |n: &u32| mask & (1 << n) != 0;
+            let mut expected: Vec<u32> = (0..8).collect();
+            expected.retain(keep);
+
+            let mut inline: Vekk<[u32; 8]> = (0..8).collect();
+            inline.retain(keep);
+            assert!(matches!(inline.repr, Repr::Inline { .. }));
+            assert_eq!(inline.as_slice(), expected.as_slice());
+
+            let mut heap: Vekk<[u32; 4]> = (0..8).collect();
+            heap.retain(keep);
+            assert!(matches!(heap.repr, Repr::Heap(_)));
+            assert_eq!(heap.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn pop_if_both_representations() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2].into();
+        assert_eq!(v.pop_if(|last| *last == 1), None);
+        assert_eq!(v.pop_if(|last| *last == 2), Some(2));
+        assert_eq!(v, [1]);
+        assert_eq!(
+            v.pop_if(|last| {
+                *last = 10;
+                false
+            }),
+            None
+        );
+        assert_eq!(v, [10]);
+
+        let mut v: Vekk<[u32; 2]> = vec![1, 2, 3].into();
+        assert_eq!(v.pop_if(|last| *last == 2), None);
+        assert_eq!(v.pop_if(|last| *last == 3), Some(3));
+        assert_eq!(v, [1, 2]);
+
+        let mut v: Vekk<[u32; 2]> = Vekk::default();
+        assert_eq!(v.pop_if(|_| true), None);
+    }
+
+    #[test]
+    fn append_exact_matching_count() {
+        let mut v: Vekk<[u32; 4]> = vec![1].into();
+        v.append_exact(3, 2..5);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [1, 2, 3, 4]);
+
+        v.append_exact(4, 5..9);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 2, 3, 4, 5, 6, 7, 8]);
+
+        v.append_exact(0, core::iter::empty());
+        assert_eq!(v.len(), 8);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "did not yield exactly 3 items")]
+    fn append_exact_count_mismatch() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        v.append_exact(3, 0..2);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "did not yield exactly 2 items")]
+    fn append_exact_extra_items() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        v.append_exact(2, 0..3);
+    }
+
+    #[cfg(not(debug_assertions))]
+    #[test]
+    fn append_exact_leaves_extra_items_in_release() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        let mut iter = 0..3;
+        v.append_exact(2, &mut iter);
+        assert_eq!(v, [0, 1]);
+        assert_eq!(iter.next(), Some(2));
+    }
+
+    #[test]
+    fn into_chunks_arrays() {
+        let v: Vekk<[u8; 8]> = (1..=8).collect();
+        let chunks: Vec<[u8; 4]> = v.into_chunks().collect();
+        assert_eq!(chunks, vec![[1, 2, 3, 4], [5, 6, 7, 8]]);
+
+        let v: Vekk<[u8; 8]> = (1..=7).collect();
+        let chunks: Vec<[u8; 3]> = v.into_chunks().collect();
+        assert_eq!(chunks, vec![[1, 2, 3], [4, 5, 6]]);
+
+        LIVE.set(0);
+        let v: Vekk<[Fragile; 2]> = (0..5).map(Fragile::new).collect();
+        assert_eq!(v.into_chunks::<2>().map(|[a, b]| a.0 + b.0).sum::<u32>(), 6);
+        assert_eq!(LIVE.get(), 0);
+    }
+
+    #[test]
+    fn set_len_heap_spare_capacity() {
+        let mut v: Vekk<[u32; 2]> = (1..=4).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        // Shrinking leaves 3 and 4 written in the spare capacity
+        unsafe { v.set_len(2) };
+        assert_eq!(v, [1, 2]);
+        unsafe { v.set_len(4) };
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "set_len 5 exceeds capacity 4")]
+    fn set_len_inline_over_capacity() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        unsafe { v.set_len(5) };
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn byte_slice_view() {
+        let v: Vekk<[u32; 4]> = vec![1, 2, 3, 0x0403_0201].into();
+        let bytes = v.as_byte_slice();
+        assert_eq!(bytes.len(), 4 * 4);
+        assert_eq!(&bytes[12..], &0x0403_0201u32.to_ne_bytes());
+
+        let v: Vekk<[u32; 4]> = Vekk::default();
+        assert!(v.as_byte_slice().is_empty());
+    }
+
+    #[test]
+    fn truncate_with_spill_policy() {
+        let spilled = || -> Vekk<[u32; 4]> {
+            let mut v = Vekk::with_capacity(16);
+            v.extend(0..3);
+            v
+        };
+
+        let mut v = spilled();
+        v.truncate_with_policy(2, SpillPolicy::Never);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [0, 1]);
+
+        let mut v = spilled();
+        v.truncate_with_policy(3, SpillPolicy::OnShrink);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.truncate_with_policy(2, SpillPolicy::OnShrink);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [0, 1]);
+
+        let mut v = spilled();
+        v.truncate_with_policy(3, SpillPolicy::Eager);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [0, 1, 2]);
+
+        let mut v: Vekk<[u32; 4]> = (0..8).collect();
+        v.truncate_with_policy(5, SpillPolicy::Eager);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(SpillPolicy::default(), SpillPolicy::Never);
+    }
+
+    #[test]
+    fn shrinking_ops_with_spill_policy() {
+        let mut v: Vekk<[u32; 2]> = (0..3).collect();
+        assert_eq!(v.pop_with_policy(SpillPolicy::Never), Some(2));
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.push(2);
+        assert_eq!(v.pop_with_policy(SpillPolicy::OnShrink), Some(2));
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+
+        let mut v: Vekk<[u32; 2]> = (0..3).collect();
+        assert_eq!(v.remove_with_policy(0, SpillPolicy::OnShrink), 0);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [1, 2]);
+
+        let mut v: Vekk<[u32; 2]> = (0..3).collect();
+        v.retain_with_policy(|n| *n != 1, SpillPolicy::OnShrink);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [0, 2]);
+    }
+
+    #[test]
+    fn reserve_fill_truncate_compact_lifecycle() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        v.reserve(100);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        v.extend(0..50);
+        assert!(v.capacity() >= 100);
+
+        v.truncate(2);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        v.compact();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.capacity(), 4);
+        assert_eq!(v, [0, 1]);
+    }
+
+    /// Yields `0..len` while reporting an arbitrary `size_hint`
+    struct LyingSizeHint {
+        inner: core::ops::Range<u32>,
+        hint: (usize, Option<usize>),
+    }
+
+    impl Iterator for LyingSizeHint {
+        type Item = u32;
+
+        fn next(&mut self) -> Option<u32> {
+            self.inner.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.hint
+        }
+    }
+
+    #[test]
+    fn from_iter_lying_size_hint() {
+        let lying = |len, hint| LyingSizeHint {
+            inner: 0..len,
+            hint,
+        };
+
+        // Over-reported upper bound, tiny actual length
+        let v: Vekk<[u32; 4]> = lying(1, (0, Some(usize::MAX))).collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[0]);
+
+        let v: Vekk<[u32; 4]> = lying(1, (0, None)).collect();
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[0]);
+
+        // Under-reported, huge actual length
+        let v: Vekk<[u32; 4]> = lying(1000, (0, Some(0))).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), (0..1000).collect::<Vec<_>>().as_slice());
+
+        let v: Vekk<[u32; 4]> = lying(1000, (0, None)).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), (0..1000).collect::<Vec<_>>().as_slice());
+
+        // Truthful lower bound beyond the inline capacity goes straight to the heap
+        let v: Vekk<[u32; 4]> = lying(10, (10, Some(10))).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), (0..10).collect::<Vec<_>>().as_slice());
+    }
 
-impl<A: Array> From<A> for Vekk<A> {
-    fn from(value: A) -> Self {
-        Self {
-            repr: Repr::Inline {
-                len: A::CAPACITY as u16,
-                array: value,
-            },
-        }
+    #[test]
+    fn take_leaves_empty_inline() {
+        let mut v: Vekk<[u32; 2]> = (0..5).collect();
+        let taken = v.take();
+        assert!(matches!(taken.repr, Repr::Heap(_)));
+        assert_eq!(taken, [0, 1, 2, 3, 4]);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert!(v.is_empty());
+
+        v.push(9);
+        assert_eq!(v.take(), [9]);
+        assert!(v.is_empty());
     }
-}
 
-impl<A: Array> From<Vec<A::Item>> for Vekk<A>
-where
-    A::Item: Default,
-{
-    fn from(value: Vec<A::Item>) -> Self {
-        value.into_iter().collect()
+    #[test]
+    fn split_at_mut_spilled() {
+        let mut v: Vekk<[u32; 2]> = (0..5).collect();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+
+        let (front, back) = v.split_at_mut(2);
+        front.swap(0, 1);
+        for (b, f) in back.iter_mut().zip(front.iter()) {
+            *b += *f * 10;
+        }
+        assert_eq!(v, [1, 0, 12, 3, 4]);
+
+        let (front, back) = v.split_at_mut(5);
+        assert_eq!(front.len(), 5);
+        assert!(back.is_empty());
     }
-}
 
-impl<A: Array> FromIterator<A::Item> for Vekk<A>
-where
-    A::Item: Default,
-{
-    fn from_iter<T: IntoIterator<Item = A::Item>>(iter: T) -> Self {
-        let mut iter = iter.into_iter();
-        match iter.size_hint() {
-            (_, Some(upper)) if upper > A::CAPACITY => Self {
-                repr: Repr::Heap(ThinVec::from_iter(iter)),
-            },
-            _ => {
-                let mut array = A::default();
-                let slice = array.as_slice_mut();
-                let mut len = 0;
+    #[test]
+    #[should_panic(expected = "index 6 out of bounds for Vekk of length 5")]
+    fn split_at_mut_out_of_bounds() {
+        let mut v: Vekk<[u32; 2]> = (0..5).collect();
+        v.split_at_mut(6);
+    }
 
-                let inline_capacity = core::cmp::min(A::CAPACITY, u16::MAX as usize);
+    #[test]
+    fn remove_indices_scattered() {
+        let mut v: Vekk<[u32; 8]> = (0..8).collect();
+        v.remove_indices(&[0, 3, 4, 7]);
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v, [1, 2, 5, 6]);
 
-                while let Some(item) = iter.next() {
-                    if len >= inline_capacity {
-                        let heap_capacity = inline_capacity + iter.size_hint().1.unwrap_or(0);
-                        let mut vec = ThinVec::with_capacity(heap_capacity);
+        let mut v: Vekk<[u32; 4]> = (0..10).collect();
+        v.remove_indices(&[1, 2, 5, 9]);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [0, 3, 4, 6, 7, 8]);
 
-                        vec.extend(array.into_iter());
-                        vec.extend(iter);
+        v.remove_indices(&[]);
+        assert_eq!(v.len(), 6);
 
-                        return Self {
-                            repr: Repr::Heap(vec),
-                        };
-                    }
+        LIVE.set(0);
+        let mut v: Vekk<[Fragile; 4]> = (0..6).map(Fragile::new).collect();
+        v.remove_indices(&[0, 5]);
+        assert_eq!(LIVE.get(), 4);
+        drop(v);
+        assert_eq!(LIVE.get(), 0);
+    }
 
-                    slice[len] = item;
-                    len += 1;
-                }
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "indices must be sorted and unique")]
+    fn remove_indices_unsorted() {
+        let mut v: Vekk<[u32; 8]> = (0..8).collect();
+        v.remove_indices(&[3, 1]);
+    }
 
-                Self {
-                    repr: Repr::Inline {
-                        len: len as u16,
-                        array,
-                    },
-                }
-            }
+    #[test]
+    fn split_first_owned_peels_to_empty() {
+        let mut v: Vekk<[char; 4]> = vec!['a', 'b', 'c'].into();
+        let mut peeled = vec![];
+        while let Some((first, rest)) = v.split_first_owned() {
+            assert!(matches!(rest.repr, Repr::Inline { .. }));
+            peeled.push(first);
+            v = rest;
         }
+        assert_eq!(peeled, ['a', 'b', 'c']);
+
+        let v: Vekk<[u32; 2]> = (0..3).collect();
+        let (first, rest) = v.split_first_owned().unwrap();
+        assert_eq!(first, 0);
+        assert!(matches!(rest.repr, Repr::Inline { .. }));
+        assert_eq!(rest, [1, 2]);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core::mem::size_of;
-    use std::num::NonZeroUsize;
+    #[test]
+    fn reserve_exact_differs_from_reserve() {
+        let mut amortized: Vekk<[u32; 4]> = (0..4).collect();
+        let mut exact = amortized.clone();
+
+        amortized.reserve(1);
+        exact.reserve_exact(1);
+        assert!(matches!(exact.repr, Repr::Heap(_)));
+        assert_eq!(exact.capacity(), 5);
+        assert!(amortized.capacity() > exact.capacity());
+
+        amortized.extend(4..amortized.capacity() as u32);
+        exact.extend(4..5);
+        amortized.reserve(1);
+        exact.reserve_exact(1);
+        assert_eq!(exact.capacity(), 6);
+        assert!(amortized.capacity() >= 2 * 8);
+
+        let mut inline: Vekk<[u32; 4]> = Vekk::default();
+        inline.reserve_exact(4);
+        assert!(matches!(inline.repr, Repr::Inline { .. }));
+    }
 
     #[test]
-    fn size() {
-        assert_eq!(size_of::<Vekk<[usize; 1]>>(), 16);
+    fn compaction_core_methods() {
+        fn check<A: Array<Item = Fragile>>(spilled: bool) {
+            let make = || -> Vekk<A> {
+                [1, 1, 2, 3, 3, 3, 4]
+                    .map(Fragile::new)
+                    .into_iter()
+                    .collect()
+            };
+            let values = |v: &Vekk<A>| v.iter().map(|f| f.0).collect::<Vec<_>>();
+            LIVE.set(0);
 
-        // Would like this to be 8 bytes, but can't manage to trick rustc into doing that
-        assert_eq!(size_of::<Vekk<[u32; 1]>>(), 16);
+            let mut v = make();
+            assert_eq!(matches!(v.repr, Repr::Heap(_)), spilled);
+            v.retain(|f| f.0 != 3);
+            assert_eq!(values(&v), [1, 1, 2, 4]);
+            assert_eq!(matches!(v.repr, Repr::Heap(_)), spilled);
+
+            v = make();
+            v.dedup_by(|a, b| a.0 == b.0);
+            assert_eq!(values(&v), [1, 2, 3, 4]);
+            assert!(matches!(v.repr, Repr::Inline { .. }));
+
+            v = make();
+            v.filter_map_in_place(|f| (f.0 != 1).then(|| Fragile::new(f.0 * 10)));
+            assert_eq!(values(&v), [20, 30, 30, 30, 40]);
+
+            v = make();
+            v.remove_indices(&[0, 2, 6]);
+            assert_eq!(values(&v), [1, 3, 3, 3]);
+
+            v = make();
+            v.retain_with_policy(|f| f.0 < 3, SpillPolicy::OnShrink);
+            assert_eq!(values(&v), [1, 1, 2]);
+            assert!(matches!(v.repr, Repr::Inline { .. }));
+
+            drop(v);
+            assert_eq!(LIVE.get(), 0);
+        }
+
+        check::<[Fragile; 8]>(false);
+        check::<[Fragile; 4]>(true);
     }
 
     #[test]
-    fn zero() {
-        let v: Vekk<[u32; 0]> = [].into_iter().collect();
+    fn from_array_without_default() {
+        let v = Vekk::<NoDefaultArray>::from_array([1, 2, 3].map(NoDefault));
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.as_slice(), &[]);
-        assert_eq!(v.iter().collect::<Vec<_>>(), Vec::<&u32>::new());
-        assert_eq!(v.into_iter().collect::<Vec<_>>(), Vec::<u32>::new());
+        assert_eq!(v.as_slice(), &[1, 2, 3].map(NoDefault));
 
-        let v: Vekk<[u32; 0]> = [42].into_iter().collect();
+        let v = Vekk::<NoDefaultArray>::from_array([1, 2, 3, 4, 5, 6].map(NoDefault));
         assert!(matches!(v.repr, Repr::Heap(_)));
-        assert_eq!(v.as_slice(), &[42]);
-        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&42]);
-        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![42]);
+        assert_eq!(v.as_slice(), &[1, 2, 3, 4, 5, 6].map(NoDefault));
+
+        let v = Vekk::<NoDefaultArray>::from_array([]);
+        assert!(v.is_empty());
+
+        let v: Vekk<[u32; 8]> = Vekk::from_array([1, 2]);
+        assert_eq!(v, [1, 2]);
     }
 
     #[test]
-    fn one() {
-        let v: Vekk<[u32; 1]> = [].into_iter().collect();
+    fn shrink_to_fit_heap_stays_heap() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        v.reserve(1000);
+        v.extend(0..10);
+        assert!(v.capacity() >= 1000);
+
+        v.shrink_to_fit();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.capacity(), v.len());
+        assert_eq!(v.as_slice(), (0..10).collect::<Vec<_>>().as_slice());
+
+        v.truncate(3);
+        v.shrink_to_fit();
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.as_slice(), &[]);
-        assert_eq!(v.iter().collect::<Vec<_>>(), Vec::<&u32>::new());
-        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![]);
+        assert_eq!(v, [0, 1, 2]);
+    }
 
-        let v: Vekk<[u32; 1]> = [42].into_iter().collect();
+    #[test]
+    fn retain_while_truncates_at_threshold() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 3, 5, 10, 2, 1].into();
+        let mut calls = 0;
+        v.retain_while(|n| {
+            calls += 1;
+            *n < 10
+        });
+        assert_eq!(calls, 4);
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.as_slice(), &[42]);
-        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&42]);
-        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![42]);
+        assert_eq!(v, [1, 3, 5]);
 
-        let v: Vekk<[u32; 1]> = [1, 2].into_iter().collect();
+        let mut v: Vekk<[u32; 2]> = vec![1, 3, 5, 10].into();
+        v.retain_while(|n| *n < 10);
         assert!(matches!(v.repr, Repr::Heap(_)));
-        assert_eq!(v.as_slice(), &[1, 2]);
-        assert_eq!(v.iter().collect::<Vec<_>>(), vec![&1, &2]);
-        assert_eq!(v.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(v, [1, 3, 5]);
+
+        v.retain_while(|_| true);
+        assert_eq!(v, [1, 3, 5]);
+        v.retain_while(|_| false);
+        assert!(v.is_empty());
     }
 
     #[test]
-    fn push_pop() {
-        let mut v: Vekk<[u32; 1]> = Default::default();
+    fn as_ptr_non_null_when_empty() {
+        let mut v: Vekk<[u64; 4]> = Vekk::default();
+        let Repr::Inline { array, .. } = &v.repr else {
+            unreachable!()
+        };
+        assert_eq!(v.as_ptr(), array.as_ptr());
+        assert!(!v.as_mut_ptr().is_null());
+
+        let mut v: Vekk<[u64; 0]> = Vekk::default();
+        assert!(!v.as_ptr().is_null());
+        assert!(v.as_ptr().is_aligned());
+        assert!(!v.as_mut_ptr().is_null());
+
+        let mut v: Vekk<[u64; 4]> = (0..8).collect();
+        v.truncate(0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert!(!v.as_ptr().is_null());
+        assert!(v.as_ptr().is_aligned());
+
+        let v = Vekk::<[u64; 4]>::from(ThinVec::new());
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert!(!v.as_ptr().is_null());
+        assert!(v.as_ptr().is_aligned());
+    }
+
+    #[test]
+    fn borrowed_iteration_reversed() {
+        fn reversed<A: Array<Item = u32>>(v: &Vekk<A>) -> Vec<u32> {
+            v.into_iter().rev().copied().collect()
+        }
+
+        let inline: Vekk<[u32; 4]> = (1..=3).collect();
+        assert_eq!(reversed(&inline), [3, 2, 1]);
+        assert_eq!(inline.iter().rev().nth(1), Some(&2));
+
+        let spilled: Vekk<[u32; 2]> = (1..=3).collect();
+        assert_eq!(reversed(&spilled), [3, 2, 1]);
+        assert_eq!(spilled.iter().rev().nth(1), Some(&2));
+    }
+
+    #[test]
+    fn swap_remove_both_representations() {
+        let mut v: Vekk<[u32; 4]> = (0..4).collect();
+        assert_eq!(v.swap_remove(1), 1);
+        assert_eq!(v, [0, 3, 2]);
+        assert_eq!(v.swap_remove(2), 2);
+        assert_eq!(v, [0, 3]);
+
+        let mut v: Vekk<[u32; 2]> = (0..4).collect();
+        assert_eq!(v.swap_remove(0), 0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [3, 1, 2]);
+    }
+
+    #[test]
+    fn removal_down_spills_past_threshold() {
+        let mut v: Vekk<[u32; 2]> = (0..5).collect();
+        assert_eq!(v.swap_remove_with_policy(0, SpillPolicy::OnShrink), 0);
+        assert_eq!(v.remove_with_policy(0, SpillPolicy::OnShrink), 4);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.swap_remove_with_policy(0, SpillPolicy::OnShrink), 1);
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.len(), 0);
-        assert_eq!(v.pop(), None);
+        assert_eq!(v, [3, 2]);
 
-        v.push(1);
+        let mut v: Vekk<[u32; 2]> = (0..4).collect();
+        v.swap_remove(0);
+        v.remove(0);
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v, [1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 4 out of bounds for Vekk of length 4")]
+    fn swap_remove_out_of_bounds() {
+        let mut v: Vekk<[u32; 2]> = (0..4).collect();
+        v.swap_remove(4);
+    }
+
+    #[test]
+    fn enumerate_indices_contiguous() {
+        fn check<A: Array<Item = u32>>(v: Vekk<A>) {
+            let len = v.len();
+            let indices: Vec<usize> = v.iter().enumerate().map(|(index, _)| index).collect();
+            assert_eq!(indices, (0..len).collect::<Vec<_>>());
+
+            let mut iter = v.into_iter();
+            assert_eq!(iter.next(), Some(0));
+            assert_eq!(iter.nth(1), Some(2));
+            let rest: Vec<(usize, u32)> = iter.enumerate().collect();
+            assert_eq!(rest, (3..len as u32).enumerate().collect::<Vec<_>>());
+        }
+
+        check::<[u32; 8]>((0..6).collect());
+        check::<[u32; 2]>((0..6).collect());
+    }
+
+    #[cfg(all(feature = "capacity-history", debug_assertions))]
+    #[test]
+    fn capacity_history_records_growth() {
+        let mut v: Vekk<[u32; 4]> = Vekk::default();
+        assert_eq!(v.capacity_history().collect::<Vec<_>>(), [4]);
+        for i in 0..20 {
+            v.push(i);
+        }
+        assert_eq!(v.capacity_history().collect::<Vec<_>>(), [4, 8, 16, 32]);
+
+        v.truncate_and_shrink(2);
+        assert_eq!(v.capacity_history().collect::<Vec<_>>(), [4, 8, 16, 32, 4]);
+
+        // Every vector counts as starting inline
+        let v: Vekk<[u32; 4]> = Vekk::with_capacity(10);
+        assert_eq!(v.capacity_history().collect::<Vec<_>>(), [4, 10]);
+    }
+
+    #[test]
+    fn try_from_slice_inline_fits_or_fails() {
+        let v = Vekk::<[u32; 4]>::try_from_slice_inline(&[1, 2, 3]).unwrap();
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.len(), 1);
-        assert_eq!(v.as_slice(), &[1]);
+        assert_eq!(v, [1, 2, 3]);
 
-        assert_eq!(v.pop(), Some(1));
+        let v = Vekk::<[u32; 4]>::try_from_slice_inline(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(v, [1, 2, 3, 4]);
+
+        assert!(Vekk::<[u32; 4]>::try_from_slice_inline(&[1, 2, 3, 4, 5]).is_err());
+        assert!(Vekk::<[u32; 4]>::try_from_slice_inline(&[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn normalize_sorted_unique() {
+        let mut v: Vekk<[u32; 4]> = vec![3, 1, 2, 3, 1].into();
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        v.normalize();
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.len(), 0);
-        assert_eq!(v.as_slice(), &[]);
+        assert_eq!(v, [1, 2, 3]);
 
-        v.push(1);
+        let mut a: Vekk<[u32; 4]> = vec![2, 2, 1].into();
+        let mut b: Vekk<[u32; 8]> = vec![1, 2, 1, 1].into();
+        a.normalize();
+        b.normalize();
+        assert_eq!(a, b);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_from_reader() {
+        fn read_all<const N: usize>(v: &mut Vekk<[u8; N]>, mut r: &[u8]) -> usize {
+            let mut reads = 0;
+            while v.read_from(&mut r).unwrap() > 0 {
+                reads += 1;
+            }
+            reads
+        }
+
+        let mut v: Vekk<[u8; 16]> = Vekk::default();
+        assert_eq!(read_all(&mut v, b"hello world"), 1);
         assert!(matches!(v.repr, Repr::Inline { .. }));
-        assert_eq!(v.as_slice(), &[1]);
+        assert_eq!(v.as_slice(), b"hello world");
 
-        v.push(2);
+        let mut v: Vekk<[u8; 4]> = "ab".into();
+        read_all(&mut v, b"hello world");
         assert!(matches!(v.repr, Repr::Heap(_)));
-        assert_eq!(v.as_slice(), &[1, 2]);
+        assert_eq!(v.as_slice(), b"abhello world");
+        assert!(v.capacity() >= v.len());
+    }
 
-        assert_eq!(v.pop(), Some(2));
-        assert!(matches!(v.repr, Repr::Heap(_)));
-        assert_eq!(v.as_slice(), &[1]);
+    #[cfg(feature = "std")]
+    #[test]
+    fn read_from_bounds() {
+        /// Claims one byte more than the buffer holds
+        struct Overreading;
+
+        impl std::io::Read for Overreading {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                Ok(buf.len() + 1)
+            }
+        }
+
+        let mut v: Vekk<[u8; 4]> = "ab".into();
+        let err = v.read_from(&mut Overreading).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(v.as_slice(), b"ab");
+
+        let mut v: Vekk<[u8; 4]> = Vekk::with_capacity(64);
+        v.extend(*b"ab");
+        let err = v.read_from(&mut Overreading).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(v.as_slice(), b"ab");
+
+        let mut v: Vekk<[u8; 4]> = Vekk::with_capacity(4 * READ_CHUNK);
+        let data = vec![7; 2 * READ_CHUNK];
+        assert_eq!(v.read_from(&mut data.as_slice()).unwrap(), READ_CHUNK);
+        assert_eq!(v.len(), READ_CHUNK);
+        assert_eq!(v.capacity(), 4 * READ_CHUNK);
     }
 
     #[test]
-    fn insert1() {
-        let mut v: Vekk<[char; 4]> = Default::default();
-        v.insert(0, 'a');
-        assert_eq!(v.as_slice(), &['a']);
+    fn run_length_encode() {
+        let v: Vekk<[u8; 8]> = vec![1, 1, 1, 2, 3, 3].into();
+        let runs: Vekk<[(u8, usize); 2]> = v.run_length_encode();
+        assert_eq!(runs.as_slice(), &[(1, 3), (2, 1), (3, 2)]);
+        assert!(matches!(runs.repr, Repr::Heap(_)));
+
+        let runs: Vekk<[(u8, usize); 4]> = v.run_length_encode();
+        assert_eq!(runs.as_slice(), &[(1, 3), (2, 1), (3, 2)]);
+        assert!(matches!(runs.repr, Repr::Inline { .. }));
+
+        let empty: Vekk<[u8; 8]> = Vekk::default();
+        assert!(empty.run_length_encode::<[(u8, usize); 4]>().is_empty());
     }
 
     #[test]
-    fn insert2() {
-        let mut v: Vekk<[char; 4]> = vec!['a', 'c'].into();
-        v.insert(1, 'b');
-        assert_eq!(v.as_slice(), &['a', 'b', 'c']);
+    fn iter_into_vekk() {
+        let v: Vekk<[u8; 4]> = vec![1, 2, 3].into();
+        let mut iter = v.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        let rest = iter.into_vekk();
+        assert_eq!(rest.as_slice(), &[2, 3]);
+        assert!(matches!(rest.repr, Repr::Inline { .. }));
+
+        let v: Vekk<[u8; 2]> = vec![1, 2, 3].into();
+        let mut iter = v.into_iter();
+        assert_eq!(iter.next(), Some(1));
+        let rest = iter.into_vekk();
+        assert_eq!(rest.as_slice(), &[2, 3]);
+        assert!(matches!(rest.repr, Repr::Heap(_)));
     }
 
     #[test]
-    fn insert3() {
-        let mut v: Vekk<[char; 4]> = vec!['a', 'b'].into();
-        v.insert(2, 'c');
-        assert_eq!(v.as_slice(), &['a', 'b', 'c']);
+    fn eq_str() {
+        let mut bytes: Vekk<[u8; 4]> = "hell".into();
+        assert_eq!(bytes, "hell");
+        assert_ne!(bytes, "hello");
+        bytes.push(b'o');
+        assert!(matches!(bytes.repr, Repr::Heap(_)));
+        assert_eq!(bytes, "hello");
+        assert_ne!(bytes, "hell");
+        assert!(bytes == *"hello");
+
+        let mut chars: Vekk<[char; 4]> = "hæll".chars().collect();
+        assert_eq!(chars, "hæll");
+        assert_ne!(chars, "hæl");
+        chars.push('o');
+        assert!(matches!(chars.repr, Repr::Heap(_)));
+        assert_eq!(chars, "hællo");
+        assert_ne!(chars, "hællå");
+        assert!(chars == *"hællo");
     }
 
     #[test]
-    fn insert4() {
-        let mut v: Vekk<[char; 4]> = vec!['a', 'b', 'd', 'e'].into();
-        assert!(matches!(v.repr, Repr::Inline { .. }));
-        v.insert(2, 'c');
-        assert_eq!(v.as_slice(), &['a', 'b', 'c', 'd', 'e']);
+    fn removal_drop_order() {
+        std::thread_local! {
+            static DROPPED: core::cell::RefCell<Vec<u32>> = const { core::cell::RefCell::new(Vec::new()) };
+        }
+
+        /// Logs its id when dropped, the default filler has none
+        #[derive(Default)]
+        struct Logged(Option<u32>);
+
+        impl Drop for Logged {
+            fn drop(&mut self) {
+                if let Some(id) = self.0 {
+                    DROPPED.with(|dropped| dropped.borrow_mut().push(id));
+                }
+            }
+        }
+
+        fn make(n: u32) -> Vekk<[Logged; 8]> {
+            (0..n).map(|id| Logged(Some(id))).collect()
+        }
+
+        fn take_dropped() -> Vec<u32> {
+            DROPPED.with(|dropped| core::mem::take(&mut *dropped.borrow_mut()))
+        }
+
+        for n in [6, 12] {
+            let mut v = make(n);
+            take_dropped();
+            v.retain(|item| item.0.unwrap().is_multiple_of(3));
+            assert_eq!(
+                take_dropped(),
+                (0..n)
+                    .filter(|id| !id.is_multiple_of(3))
+                    .collect::<Vec<_>>()
+            );
+
+            let mut v = make(n);
+            take_dropped();
+            v.truncate(2);
+            assert_eq!(take_dropped(), (2..n).collect::<Vec<_>>());
+
+            let mut v = make(n);
+            take_dropped();
+            v.drain_front(4);
+            assert_eq!(take_dropped(), [0, 1, 2, 3]);
+            drop(v);
+            take_dropped();
+        }
     }
 
     #[test]
-    fn insert_extend() {
-        let mut v: Vekk<[char; 4]> = Default::default();
+    fn recommended_capacity() {
+        type V = Vekk<[u8; 4]>;
+        assert_eq!(V::recommended_capacity(0), 4);
+        assert_eq!(V::recommended_capacity(4), 4);
+        assert_eq!(V::recommended_capacity(5), 8);
+        assert_eq!(V::recommended_capacity(8), 8);
+        assert_eq!(V::recommended_capacity(9), 16);
+        assert_eq!(V::recommended_capacity(usize::MAX), usize::MAX);
 
-        v.insert(0, 'b');
-        assert_eq!(v.as_slice(), &['b']);
+        for expected_len in 0..40 {
+            let mut v = V::default();
+            for _ in 0..expected_len {
+                v.push(0);
+            }
+            assert_eq!(v.capacity(), V::recommended_capacity(expected_len));
+        }
 
-        v.extend(['d']);
-        assert_eq!(v.as_slice(), &['b', 'd']);
+        assert_eq!(Vekk::<[u8; 0]>::recommended_capacity(0), 0);
+        assert_eq!(Vekk::<[u8; 0]>::recommended_capacity(1), 1);
+        assert_eq!(Vekk::<[u8; 0]>::recommended_capacity(3), 4);
+    }
 
-        v.insert(1, 'c');
-        assert_eq!(v.as_slice(), &['b', 'c', 'd']);
+    #[test]
+    fn borrowed_iter_traits() {
+        fn assert_traits<I>(iter: I) -> I
+        where
+            I: DoubleEndedIterator + ExactSizeIterator + core::iter::FusedIterator,
+        {
+            iter
+        }
+
+        fn check<A: Array<Item = u8>>(v: &Vekk<A>) {
+            let mut iter = assert_traits(v.into_iter());
+            assert_eq!(iter.len(), 3);
+            assert_eq!(iter.next_back(), Some(&3));
+            assert_eq!(iter.len(), 2);
+            assert_eq!(iter.clone().rev().collect::<Vec<_>>(), [&2, &1]);
+            assert_eq!(iter.next(), Some(&1));
+            assert_eq!(iter.next(), Some(&2));
+            assert_eq!(iter.len(), 0);
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next(), None);
+            assert_eq!(iter.next_back(), None);
+        }
+
+        let inline: Vekk<[u8; 4]> = vec![1, 2, 3].into();
+        assert!(matches!(inline.repr, Repr::Inline { .. }));
+        check(&inline);
+
+        let heap: Vekk<[u8; 2]> = vec![1, 2, 3].into();
+        assert!(matches!(heap.repr, Repr::Heap(_)));
+        check(&heap);
+    }
+
+    #[test]
+    fn push_and_get_mut() {
+        use alloc::string::{String, ToString};
+
+        let mut v: Vekk<[String; 2]> = Vekk::default();
+        v.push_and_get_mut("a".to_string()).push('1');
+        v.push_and_get_mut("b".to_string()).push('2');
         assert!(matches!(v.repr, Repr::Inline { .. }));
 
-        v.insert(3, 'e');
-        assert_eq!(v.as_slice(), &['b', 'c', 'd', 'e']);
+        let spilled = v.push_and_get_mut("c".to_string());
+        spilled.push('3');
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), ["a1", "b2", "c3"]);
+    }
+
+    #[test]
+    fn resize_down_spills() {
+        let mut v: Vekk<[u32; 4]> = vec![1, 2, 3, 4, 5, 6].into();
+        v.resize(2, 0);
         assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 2]);
 
-        v.insert(0, 'a');
-        assert_eq!(v.as_slice(), &['a', 'b', 'c', 'd', 'e']);
+        let mut next = 0;
+        v.resize_with(6, || {
+            next += 1;
+            next
+        });
         assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 1, 2, 3, 4]);
+
+        v.resize_with(5, || unreachable!());
+        assert!(matches!(v.repr, Repr::Heap(_)));
+        assert_eq!(v.as_slice(), &[1, 2, 1, 2, 3]);
+
+        v.resize_with(2, || unreachable!());
+        assert!(matches!(v.repr, Repr::Inline { .. }));
+        assert_eq!(v.as_slice(), &[1, 2]);
+    }
+
+    #[test]
+    fn iter_as_ref() {
+        fn total(items: impl AsRef<[u32]>) -> u32 {
+            items.as_ref().iter().sum()
+        }
+
+        let mut inline = Vekk::<[u32; 4]>::from(vec![1, 2, 3]).into_iter();
+        inline.next();
+        assert_eq!(total(&inline), 5);
+        assert_eq!(inline.as_ref(), &[2, 3]);
+
+        let mut heap = Vekk::<[u32; 2]>::from(vec![1, 2, 3]).into_iter();
+        heap.next();
+        assert_eq!(total(&heap), 5);
+        assert_eq!(heap.as_ref(), &[2, 3]);
+    }
+
+    #[test]
+    fn clone_inline_copy() {
+        let v: Vekk<[u8; 256]> = (0..=255).collect();
+        let mut clone = v.clone();
+        assert!(matches!(clone.repr, Repr::Inline { .. }));
+        assert_eq!(clone, v);
+
+        clone[0] = 42;
+        clone.pop();
+        assert_eq!(v[0], 0);
+        assert_eq!(v.len(), 256);
+        assert_eq!(clone.as_slice(), [&[42], &v[1..255]].concat());
+    }
+
+    #[test]
+    fn into_chunks_with_remainder() {
+        let v: Vekk<[u32; 4]> = (1..=7).collect();
+        let (chunks, remainder) = v.into_chunks_with_remainder::<3>();
+        assert_eq!(chunks.collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(remainder.as_slice(), &[7]);
+        assert!(matches!(remainder.repr, Repr::Inline { .. }));
+
+        let v: Vekk<[u32; 4]> = (1..=6).collect();
+        let (chunks, remainder) = v.into_chunks_with_remainder::<3>();
+        assert_eq!(chunks.count(), 2);
+        assert!(remainder.is_empty());
+
+        let v: Vekk<[u32; 4]> = (1..=2).collect();
+        let (mut chunks, remainder) = v.into_chunks_with_remainder::<3>();
+        assert_eq!(chunks.next(), None);
+        assert_eq!(remainder.as_slice(), &[1, 2]);
     }
 
     #[allow(unused)]
@@ -0,0 +1,85 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use ::serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use ::serde::ser::{Serialize, Serializer};
+
+use crate::{Array, Vekk};
+
+/// Upper bound on elements reserved up front from an untrusted size hint
+const MAX_PREALLOCATION: usize = 4096;
+
+impl<A: Array> Serialize for Vekk<A>
+where
+    A::Item: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, A: Array> Deserialize<'de> for Vekk<A>
+where
+    A::Item: Deserialize<'de> + Default,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(VekkVisitor {
+            max: usize::MAX,
+            marker: PhantomData,
+        })
+    }
+}
+
+impl<A: Array> Vekk<A> {
+    /// Deserialize a sequence of at most `max` elements.
+    ///
+    /// Longer sequences fail with an `invalid_length` error as soon as the
+    /// limit is exceeded, without buffering the rest of the input.
+    pub fn deserialize_bounded<'de, D>(deserializer: D, max: usize) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        A::Item: Deserialize<'de> + Default,
+    {
+        deserializer.deserialize_seq(VekkVisitor {
+            max,
+            marker: PhantomData,
+        })
+    }
+}
+
+struct VekkVisitor<A> {
+    max: usize,
+    marker: PhantomData<A>,
+}
+
+impl<'de, A: Array> Visitor<'de> for VekkVisitor<A>
+where
+    A::Item: Deserialize<'de> + Default,
+{
+    type Value = Vekk<A>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.max == usize::MAX {
+            f.write_str("a sequence")
+        } else {
+            write!(f, "a sequence of at most {} elements", self.max)
+        }
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let size_hint = seq.size_hint().unwrap_or(0);
+        if size_hint > self.max {
+            return Err(S::Error::invalid_length(size_hint, &self));
+        }
+
+        let mut vekk = Vekk::with_capacity(core::cmp::min(size_hint, MAX_PREALLOCATION));
+        while let Some(item) = seq.next_element()? {
+            if vekk.len() == self.max {
+                return Err(S::Error::invalid_length(self.max + 1, &self));
+            }
+            vekk.push(item);
+        }
+
+        Ok(vekk)
+    }
+}
@@ -0,0 +1,97 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use vekk::Vekk;
+
+struct CountingAllocator;
+
+std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.set(ALLOCATIONS.get() + 1);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Allocations made by the current thread while running `f`
+fn allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.get();
+    f();
+    ALLOCATIONS.get() - before
+}
+
+#[test]
+fn inline_does_not_allocate() {
+    let count = allocations(|| {
+        let mut v: Vekk<[u64; 8]> = Vekk::default();
+        for i in 0..4 {
+            v.push(i);
+        }
+        v.extend(4..7);
+        v.insert(0, 42);
+        assert_eq!(v.len(), 8);
+        assert_eq!(v.pop(), Some(6));
+        assert_eq!(v.iter().sum::<u64>(), 42 + 15);
+
+        let w = v.clone();
+        assert_eq!(w.into_iter().count(), 7);
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn inline_extend_vekks_does_not_allocate() {
+    let count = allocations(|| {
+        let mut v: Vekk<[u64; 8]> = (0..2).collect();
+        v.extend_vekks([
+            (2..4).collect::<Vekk<[u64; 8]>>(),
+            Vekk::default(),
+            (4..7).collect(),
+        ]);
+        assert_eq!(v.as_slice(), &[0, 1, 2, 3, 4, 5, 6]);
+
+        let mut empty: Vekk<[u64; 8]> = Vekk::default();
+        empty.extend_vekks([v, (7..8).collect()]);
+        assert_eq!(empty.len(), 8);
+    });
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn spilling_allocates() {
+    let count = allocations(|| {
+        let mut v: Vekk<[u64; 8]> = (0..8).collect();
+        v.push(8);
+        assert_eq!(v.len(), 9);
+    });
+    assert!(count > 0);
+}
+
+#[test]
+fn enumerate_owning_heap_iterator_does_not_allocate() {
+    let v: Vekk<[u64; 2]> = (10..20).collect();
+    let count = allocations(|| {
+        let mut iter = v.into_iter().enumerate();
+        assert_eq!(iter.next(), Some((0, 10)));
+        for (expected, (index, item)) in (1..).zip(iter) {
+            assert_eq!(index, expected);
+            assert_eq!(item, 10 + expected as u64);
+        }
+    });
+    assert_eq!(count, 0);
+}
@@ -0,0 +1,18 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use vekk::Vekk;
+
+#[test]
+fn no_std_push_and_collect() {
+    let mut v: Vekk<[u32; 2]> = Vekk::default();
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    assert_eq!(v.as_slice(), &[1, 2, 3]);
+
+    let collected: Vec<u32> = v.into_iter().collect();
+    assert_eq!(collected, [1, 2, 3]);
+}